@@ -1,6 +1,31 @@
+use std::env;
+
+use graph_lib::graph::load;
+
 fn main() {
-    let graph = graph_lib::make_graph();
-    let search_results = match graph.depth_first_search("Seattle, WA", "Miami, FL") {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let graph = match args.as_slice() {
+        [nodes, edges] => load::from_files(nodes, edges),
+        [combined] => load::from_file(combined),
+        _ => Ok(graph_lib::make_graph()),
+    };
+    let graph = match graph {
+        Ok(graph) => graph,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+
+    let (start, end) = match (graph.id("Seattle, WA"), graph.id("Miami, FL")) {
+        (Ok(start), Ok(end)) => (start, end),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+
+    let search_results = match graph.depth_first_search(start, end) {
         Ok(results) => results,
         Err(e) => {
             eprintln!("{}", e);
@@ -11,7 +36,7 @@ fn main() {
     match search_results {
         Some(result) => {
             for (id, dist) in result.iter() {
-                println!("{}: {}", id, dist);
+                println!("{}: {}", graph.name(*id).unwrap(), dist);
             }
         }
         None => {
@@ -19,7 +44,7 @@ fn main() {
         }
     }
 
-    let bfs_results = match graph.breadth_first_search("Seattle, WA", "Miami, FL") {
+    let bfs_results = match graph.breadth_first_search(start, end) {
         Ok(results) => results,
         Err(e) => {
             eprintln!("{}J", e);
@@ -30,7 +55,7 @@ fn main() {
     match bfs_results {
         Some(results) => {
             for (id, dist) in results.iter() {
-                println!("{}: {}", id, dist);
+                println!("{}: {}", graph.name(*id).unwrap(), dist);
             }
         }
         None => {
@@ -38,7 +63,7 @@ fn main() {
         }
     }
 
-    let dij_results = match graph.shortest_path("Seattle, WA", "Miami, FL") {
+    let dij_results = match graph.shortest_path(start, end) {
         Ok(results) => results,
         Err(e) => {
             eprintln!("{}J", e);
@@ -49,7 +74,7 @@ fn main() {
     match dij_results {
         Some(results) => {
             for (id, dist) in results.iter() {
-                println!("{}: {}", id, dist);
+                println!("{}: {}", graph.name(*id).unwrap(), dist);
             }
         }
         None => {
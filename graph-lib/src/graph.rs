@@ -1,165 +1,926 @@
+pub mod all_pairs;
+pub mod components;
+pub mod cut;
+pub mod cycle;
+mod error;
+pub mod flow;
+mod interner;
+pub mod load;
+pub mod metric;
+pub mod metrics;
+pub mod mst;
 pub mod node;
 pub mod point;
 pub mod search;
+pub mod storage;
+pub mod topo;
+pub mod tree;
+pub mod tsp;
+pub mod yen;
 
 use std::collections::HashMap;
-use std::error::Error;
-use std::fmt;
-
-use node::Node;
+use std::ops::Index;
+
+use all_pairs::DistanceMatrix;
+use components::Components;
+use cut::Cuts;
+pub use error::GraphError;
+use flow::MaxFlow;
+use interner::Interner;
+use metric::Metric;
+use mst::{kruskal, prim, MinimumSpanningTree, SpanningTree};
+use node::{Node, NodeId};
 use point::Point;
-use search::{bfs, dfs, dijk, Search, Status};
+use search::{
+    astar, beam, bellman, bfs, bibfs, bidijk, dfs, dijk, dls, greedy, iddfs, Search, SearchOptions,
+    Status,
+};
+use storage::csr::CompressedSparseRow;
+use storage::map::AdjacencyMap;
+use storage::{Neighbours, Storage};
+use tree::ShortestPathTree;
+use tsp::Tour;
 
 #[derive(Debug, Clone)]
 pub struct Graph {
-    nodes: HashMap<String, Node>,
+    nodes: Vec<Option<Node>>,
+    names: Interner,
+    storage: Box<dyn Storage>,
+    // How much each edge can carry, for max_flow. Kept apart from the
+    // storage since most graphs never set any.
+    capacities: HashMap<(NodeId, NodeId), f64>,
+    len: usize,
+    metric: Metric,
 }
 
 // Associate functions
 impl Graph {
     pub fn new() -> Graph {
+        Graph::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Graph {
         Graph {
-            nodes: HashMap::new(),
+            nodes: Vec::with_capacity(capacity),
+            names: Interner::with_capacity(capacity),
+            storage: Box::new(AdjacencyMap::new()),
+            capacities: HashMap::new(),
+            len: 0,
+            metric: Metric::default(),
         }
     }
 
-    pub fn with_capacity(capacity: usize) -> Graph {
+    pub fn with_metric(metric: Metric) -> Graph {
         Graph {
-            nodes: HashMap::with_capacity(capacity),
+            metric,
+            ..Graph::new()
         }
     }
 }
 
+impl Default for Graph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // Public methods
 impl Graph {
-    pub fn nodes(&self) -> &HashMap<String, Node> {
-        &self.nodes
+    pub fn nodes(&self) -> impl Iterator<Item = &Node> + '_ {
+        self.nodes.iter().flatten()
     }
 
-    pub fn add_node(&mut self, id: &str, point: Point) {
-        let node = Node::new(id, point);
+    // One more than the largest NodeId handed out so far. Removed nodes
+    // leave gaps, so this can be larger than len().
+    pub fn node_bound(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn metric(&self) -> Metric {
+        self.metric
+    }
 
-        self.nodes.insert(id.to_owned(), node);
+    pub fn storage(&self) -> &dyn Storage {
+        &*self.storage
     }
 
-    pub fn remove_node(&mut self, id: &str) -> Option<Node> {
-        for (_, node) in self.nodes.iter_mut() {
-            node.remove_edge(id);
+    // Moves every node and edge into a new, empty storage of type S.
+    pub fn set_storage<S: Storage + Default + 'static>(&mut self) -> Result<(), GraphError> {
+        let mut storage = S::default();
+        for i in 0..self.node_bound() {
+            storage.add_node(NodeId::new(i))?;
+        }
+        for i in 0..self.node_bound() {
+            let id = NodeId::new(i);
+            for (other_id, weight) in self.edges(id) {
+                storage.add_edge(id, other_id, weight)?;
+            }
         }
 
-        self.nodes.remove(id)
+        self.storage = Box::new(storage);
+
+        Ok(())
+    }
+
+    // Packs the edges into a compressed sparse row layout. The graph can
+    // still be searched, but adding or removing nodes and edges will fail
+    // with GraphError::Frozen until set_storage is called.
+    pub fn freeze(&mut self) {
+        self.storage = Box::new(CompressedSparseRow::from_storage(
+            &*self.storage,
+            self.node_bound(),
+        ));
+    }
+
+    pub fn edges(&self, id: NodeId) -> Neighbours<'_> {
+        self.storage.neighbours(id)
+    }
+
+    pub fn incoming(&self, id: NodeId) -> Neighbours<'_> {
+        self.storage.incoming(id)
+    }
+
+    pub fn weight(&self, id: NodeId, other_id: NodeId) -> Option<f64> {
+        self.storage.weight(id, other_id)
+    }
+
+    // None if the edge has no capacity set, or doesn't exist.
+    pub fn capacity(&self, id: NodeId, other_id: NodeId) -> Option<f64> {
+        self.capacities.get(&(id, other_id)).copied()
+    }
+
+    pub fn id(&self, name: &str) -> Result<NodeId, GraphError> {
+        self.names
+            .get(name)
+            .ok_or_else(|| GraphError::UnknownNode(name.to_owned()))
     }
 
-    pub fn add_edge(&mut self, id: &str, other_id: &str) {
-        let dist = self.calc_dist(id, other_id);
+    pub fn name(&self, id: NodeId) -> Result<&str, GraphError> {
+        self.node(id)?;
 
-        if let Some(n) = self.nodes.get_mut(id) {
-            n.add_edge(other_id, dist);
+        Ok(self.names.name(id).unwrap())
+    }
+
+    pub fn node(&self, id: NodeId) -> Result<&Node, GraphError> {
+        match self.nodes.get(id.index()) {
+            Some(Some(node)) => Ok(node),
+            _ => Err(GraphError::UnknownId(id)),
         }
+    }
 
-        if let Some(n) = self.nodes.get_mut(other_id) {
-            n.add_edge(id, dist);
+    pub fn add_node(&mut self, name: &str, point: Point) -> Result<NodeId, GraphError> {
+        if self.names.get(name).is_some() {
+            return Err(GraphError::DuplicateNode(name.to_owned()));
+        }
+        if !point.row().is_finite() || !point.col().is_finite() {
+            return Err(GraphError::InvalidPoint(point.row(), point.col()));
         }
+
+        let id = NodeId::new(self.node_bound());
+        self.storage.add_node(id)?;
+        self.names.intern(name);
+        self.nodes.push(Some(Node::new(id, point)));
+        self.len += 1;
+
+        Ok(id)
     }
 
-    pub fn add_directed_edge(&mut self, id: &str, other_id: &str) {
-        let dist = self.calc_dist(id, other_id);
+    pub fn remove_node(&mut self, id: NodeId) -> Result<Node, GraphError> {
+        self.node(id)?;
+        self.storage.remove_node(id)?;
 
-        if let Some(n) = self.nodes.get_mut(id) {
-            n.add_edge(other_id, dist);
-        }
+        self.capacities
+            .retain(|(one, two), _| *one != id && *two != id);
+        let node = self.nodes[id.index()].take().unwrap();
+        self.names.forget(id);
+        self.len -= 1;
+
+        Ok(node)
     }
 
-    pub fn remove_edge(&mut self, id: &str, remove_id: &str) -> Option<f64> {
-        if let Some(n) = self.nodes.get_mut(id) {
-            n.remove_edge(remove_id)
-        } else {
-            None
+    pub fn add_edge(&mut self, id: NodeId, other_id: NodeId) -> Result<(), GraphError> {
+        let dist = self.calc_dist(id, other_id)?;
+
+        self.storage.add_edge(id, other_id, dist)?;
+        self.storage.add_edge(other_id, id, dist)?;
+
+        Ok(())
+    }
+
+    pub fn add_directed_edge(&mut self, id: NodeId, other_id: NodeId) -> Result<(), GraphError> {
+        let dist = self.calc_dist(id, other_id)?;
+
+        self.storage.add_edge(id, other_id, dist)?;
+
+        Ok(())
+    }
+
+    pub fn add_weighted_edge(
+        &mut self,
+        id: NodeId,
+        other_id: NodeId,
+        weight: f64,
+    ) -> Result<(), GraphError> {
+        self.check_edge(id, other_id, weight)?;
+        // Going there and back would be a negative cycle.
+        if weight < 0.0 {
+            return Err(GraphError::NegativeWeight(weight));
         }
+
+        self.storage.add_edge(id, other_id, weight)?;
+        self.storage.add_edge(other_id, id, weight)?;
+
+        Ok(())
+    }
+
+    // Negative weights are allowed, but only bellman_ford and
+    // floyd_warshall find correct shortest paths on a graph that has them.
+    // Everything else measuring distance assumes weights aren't negative.
+    pub fn add_directed_weighted_edge(
+        &mut self,
+        id: NodeId,
+        other_id: NodeId,
+        weight: f64,
+    ) -> Result<(), GraphError> {
+        self.check_edge(id, other_id, weight)?;
+
+        self.storage.add_edge(id, other_id, weight)?;
+
+        Ok(())
+    }
+
+    pub fn remove_edge(
+        &mut self,
+        id: NodeId,
+        remove_id: NodeId,
+    ) -> Result<Option<f64>, GraphError> {
+        self.node(id)?;
+        self.node(remove_id)?;
+
+        let weight = self.storage.remove_edge(id, remove_id)?;
+        self.capacities.remove(&(id, remove_id));
+
+        Ok(weight)
+    }
+
+    // Sets the capacity of the edges both ways, like add_edge.
+    pub fn set_capacity(
+        &mut self,
+        id: NodeId,
+        other_id: NodeId,
+        capacity: f64,
+    ) -> Result<(), GraphError> {
+        self.check_capacity(id, other_id, capacity)?;
+        self.check_capacity(other_id, id, capacity)?;
+
+        self.capacities.insert((id, other_id), capacity);
+        self.capacities.insert((other_id, id), capacity);
+
+        Ok(())
+    }
+
+    pub fn set_directed_capacity(
+        &mut self,
+        id: NodeId,
+        other_id: NodeId,
+        capacity: f64,
+    ) -> Result<(), GraphError> {
+        self.check_capacity(id, other_id, capacity)?;
+
+        self.capacities.insert((id, other_id), capacity);
+
+        Ok(())
     }
 
     pub fn len(&self) -> usize {
-        self.nodes.len()
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
     }
 
     pub fn depth_first_search(
         &self,
-        start: &str,
-        end: &str,
-    ) -> Result<Option<Vec<(String, f64)>>, DoesNotContainError> {
-        if !self.nodes.contains_key(start) || !self.nodes.contains_key(end) {
-            return Err(DoesNotContainError);
-        }
-
-        let mut search = dfs::DepthFirstSearch::new(&self, start, end);
+        start: NodeId,
+        end: NodeId,
+    ) -> Result<Option<Vec<(NodeId, f64)>>, GraphError> {
+        let mut search = dfs::DepthFirstSearch::new(self, start, end)?;
 
         while let Status::Searching = search.next() {}
 
         Ok(search.result())
     }
 
-    pub fn step_depth_first_search(&self, start: &str, end: &str) -> dfs::DepthFirstSearch {
+    pub fn step_depth_first_search(
+        &self,
+        start: NodeId,
+        end: NodeId,
+    ) -> Result<dfs::DepthFirstSearch<'_>, GraphError> {
         dfs::DepthFirstSearch::new(self, start, end)
     }
 
-    pub fn breadth_first_search(
+    pub fn depth_first_search_with(
         &self,
-        start: &str,
-        end: &str,
-    ) -> Result<Option<Vec<(String, f64)>>, DoesNotContainError> {
-        if !self.nodes.contains_key(start) || !self.nodes.contains_key(end) {
-            return Err(DoesNotContainError);
-        }
+        start: NodeId,
+        end: NodeId,
+        options: SearchOptions,
+    ) -> Result<Option<Vec<(NodeId, f64)>>, GraphError> {
+        let mut search = dfs::DepthFirstSearch::with_options(self, start, end, options)?;
+
+        while let Status::Searching = search.next() {}
+
+        Ok(search.result())
+    }
+
+    pub fn step_depth_first_search_with(
+        &self,
+        start: NodeId,
+        end: NodeId,
+        options: SearchOptions,
+    ) -> Result<dfs::DepthFirstSearch<'_>, GraphError> {
+        dfs::DepthFirstSearch::with_options(self, start, end, options)
+    }
+
+    pub fn depth_limited_search(
+        &self,
+        start: NodeId,
+        end: NodeId,
+        limit: usize,
+    ) -> Result<Option<Vec<(NodeId, f64)>>, GraphError> {
+        let mut search = dls::DepthLimitedSearch::new(self, start, end, limit)?;
+
+        while let Status::Searching = search.next() {}
+
+        Ok(search.result())
+    }
+
+    pub fn step_depth_limited_search(
+        &self,
+        start: NodeId,
+        end: NodeId,
+        limit: usize,
+    ) -> Result<dls::DepthLimitedSearch<'_>, GraphError> {
+        dls::DepthLimitedSearch::new(self, start, end, limit)
+    }
 
-        let mut search = bfs::BreadthFirstSearch::new(&self, start, end);
+    pub fn iterative_deepening_search(
+        &self,
+        start: NodeId,
+        end: NodeId,
+    ) -> Result<Option<Vec<(NodeId, f64)>>, GraphError> {
+        let mut search = iddfs::IterativeDeepeningSearch::new(self, start, end)?;
 
         while let Status::Searching = search.next() {}
 
         Ok(search.result())
     }
 
-    pub fn step_breadth_first_search(&self, start: &str, end: &str) -> bfs::BreadthFirstSearch {
+    pub fn step_iterative_deepening_search(
+        &self,
+        start: NodeId,
+        end: NodeId,
+    ) -> Result<iddfs::IterativeDeepeningSearch<'_>, GraphError> {
+        iddfs::IterativeDeepeningSearch::new(self, start, end)
+    }
+
+    pub fn breadth_first_search(
+        &self,
+        start: NodeId,
+        end: NodeId,
+    ) -> Result<Option<Vec<(NodeId, f64)>>, GraphError> {
+        let mut search = bfs::BreadthFirstSearch::new(self, start, end)?;
+
+        while let Status::Searching = search.next() {}
+
+        Ok(search.result())
+    }
+
+    pub fn step_breadth_first_search(
+        &self,
+        start: NodeId,
+        end: NodeId,
+    ) -> Result<bfs::BreadthFirstSearch<'_>, GraphError> {
         bfs::BreadthFirstSearch::new(self, start, end)
     }
 
+    pub fn breadth_first_search_with(
+        &self,
+        start: NodeId,
+        end: NodeId,
+        options: SearchOptions,
+    ) -> Result<Option<Vec<(NodeId, f64)>>, GraphError> {
+        let mut search = bfs::BreadthFirstSearch::with_options(self, start, end, options)?;
+
+        while let Status::Searching = search.next() {}
+
+        Ok(search.result())
+    }
+
+    pub fn step_breadth_first_search_with(
+        &self,
+        start: NodeId,
+        end: NodeId,
+        options: SearchOptions,
+    ) -> Result<bfs::BreadthFirstSearch<'_>, GraphError> {
+        bfs::BreadthFirstSearch::with_options(self, start, end, options)
+    }
+
+    pub fn bidirectional_breadth_first_search(
+        &self,
+        start: NodeId,
+        end: NodeId,
+    ) -> Result<Option<Vec<(NodeId, f64)>>, GraphError> {
+        let mut search = bibfs::BidirectionalBreadthFirstSearch::new(self, start, end)?;
+
+        while let Status::Searching = search.next() {}
+
+        Ok(search.result())
+    }
+
+    pub fn step_bidirectional_breadth_first_search(
+        &self,
+        start: NodeId,
+        end: NodeId,
+    ) -> Result<bibfs::BidirectionalBreadthFirstSearch<'_>, GraphError> {
+        bibfs::BidirectionalBreadthFirstSearch::new(self, start, end)
+    }
+
     pub fn shortest_path(
         &self,
-        start: &str,
-        end: &str,
-    ) -> Result<Option<Vec<(String, f64)>>, DoesNotContainError> {
-        if !self.nodes.contains_key(start) || !self.nodes.contains_key(end) {
-            return Err(DoesNotContainError);
+        start: NodeId,
+        end: NodeId,
+    ) -> Result<Option<Vec<(NodeId, f64)>>, GraphError> {
+        let mut search = dijk::ShortestPath::new(self, start, end)?;
+
+        while let Status::Searching = search.next() {}
+
+        Ok(search.result())
+    }
+
+    pub fn step_shortest_path(
+        &self,
+        start: NodeId,
+        end: NodeId,
+    ) -> Result<dijk::ShortestPath<'_>, GraphError> {
+        dijk::ShortestPath::new(self, start, end)
+    }
+
+    pub fn shortest_path_with(
+        &self,
+        start: NodeId,
+        end: NodeId,
+        options: SearchOptions,
+    ) -> Result<Option<Vec<(NodeId, f64)>>, GraphError> {
+        let mut search = dijk::ShortestPath::with_options(self, start, end, options)?;
+
+        while let Status::Searching = search.next() {}
+
+        Ok(search.result())
+    }
+
+    pub fn step_shortest_path_with(
+        &self,
+        start: NodeId,
+        end: NodeId,
+        options: SearchOptions,
+    ) -> Result<dijk::ShortestPath<'_>, GraphError> {
+        dijk::ShortestPath::with_options(self, start, end, options)
+    }
+
+    pub fn bidirectional_shortest_path(
+        &self,
+        start: NodeId,
+        end: NodeId,
+    ) -> Result<Option<Vec<(NodeId, f64)>>, GraphError> {
+        let mut search = bidijk::BidirectionalShortestPath::new(self, start, end)?;
+
+        while let Status::Searching = search.next() {}
+
+        Ok(search.result())
+    }
+
+    pub fn step_bidirectional_shortest_path(
+        &self,
+        start: NodeId,
+        end: NodeId,
+    ) -> Result<bidijk::BidirectionalShortestPath<'_>, GraphError> {
+        bidijk::BidirectionalShortestPath::new(self, start, end)
+    }
+
+    // Handles negative edge weights, but fails if the start can reach a
+    // cycle whose weights add up to less than zero.
+    pub fn bellman_ford(
+        &self,
+        start: NodeId,
+        end: NodeId,
+    ) -> Result<Option<Vec<(NodeId, f64)>>, GraphError> {
+        let mut search = bellman::BellmanFord::new(self, start, end)?;
+
+        while let Status::Searching = search.next() {}
+
+        if let Some(cycle) = search.negative_cycle() {
+            return Err(GraphError::NegativeCycle(cycle.to_vec()));
         }
 
-        let mut search = dijk::ShortestPath::new(&self, start, end);
+        Ok(search.result())
+    }
+
+    pub fn step_bellman_ford(
+        &self,
+        start: NodeId,
+        end: NodeId,
+    ) -> Result<bellman::BellmanFord<'_>, GraphError> {
+        bellman::BellmanFord::new(self, start, end)
+    }
+
+    // Up to k loopless paths, cheapest first. Fewer come back if there
+    // aren't k distinct ones.
+    pub fn k_shortest_paths(
+        &self,
+        start: NodeId,
+        end: NodeId,
+        k: usize,
+    ) -> Result<Vec<Vec<(NodeId, f64)>>, GraphError> {
+        yen::k_shortest_paths(self, start, end, k)
+    }
+
+    pub fn max_flow(&self, source: NodeId, sink: NodeId) -> Result<MaxFlow, GraphError> {
+        flow::edmonds_karp(self, source, sink)
+    }
+
+    pub fn connected_components(&self) -> Components {
+        Components::connected(self)
+    }
+
+    pub fn strongly_connected_components(&self) -> Components {
+        Components::strongly_connected(self)
+    }
+
+    // Articulation points and bridges, treating every edge as two-way.
+    pub fn cuts(&self) -> Cuts {
+        cut::find(self)
+    }
+
+    // Every edge points forward in the order.
+    pub fn topological_sort(&self) -> Result<Vec<NodeId>, GraphError> {
+        topo::sort(self)
+    }
+
+    pub fn find_cycle(&self) -> Option<Vec<NodeId>> {
+        cycle::find_directed(self)
+    }
+
+    pub fn find_undirected_cycle(&self) -> Option<Vec<NodeId>> {
+        cycle::find_undirected(self)
+    }
+
+    pub fn prim(&self, start: NodeId) -> Result<MinimumSpanningTree, GraphError> {
+        let mut tree = prim::Prim::new(self, start)?;
+
+        while let Status::Searching = tree.next() {}
+
+        Ok(tree.result().unwrap())
+    }
+
+    pub fn step_prim(&self, start: NodeId) -> Result<prim::Prim<'_>, GraphError> {
+        prim::Prim::new(self, start)
+    }
+
+    pub fn kruskal(&self) -> MinimumSpanningTree {
+        let mut tree = kruskal::Kruskal::new(self);
+
+        while let Status::Searching = tree.next() {}
+
+        tree.result().unwrap()
+    }
+
+    pub fn step_kruskal(&self) -> kruskal::Kruskal<'_> {
+        kruskal::Kruskal::new(self)
+    }
+
+    // The cheapest round trip through the stops, starting and ending at the
+    // first. Exact, but only for up to tsp::HELD_KARP_LIMIT stops.
+    pub fn held_karp_tour(&self, stops: &[NodeId]) -> Result<Tour, GraphError> {
+        let costs = tsp::Costs::new(self, &tsp::unique(stops))?;
+        let order = tsp::held_karp(&costs, true)?;
+
+        costs.tour(&order, true)
+    }
+
+    // A quick round trip that always goes to the closest stop next.
+    pub fn nearest_neighbour_tour(&self, stops: &[NodeId]) -> Result<Tour, GraphError> {
+        let costs = tsp::Costs::new(self, &tsp::unique(stops))?;
+        let order = tsp::nearest_neighbour(&costs, true);
+
+        costs.tour(&order, true)
+    }
+
+    // The nearest neighbour tour, improved with 2-opt moves until none help.
+    pub fn two_opt_tour(&self, stops: &[NodeId]) -> Result<Tour, GraphError> {
+        let costs = tsp::Costs::new(self, &tsp::unique(stops))?;
+        let order = tsp::two_opt(&costs, tsp::nearest_neighbour(&costs, true), true);
+
+        costs.tour(&order, true)
+    }
+
+    // Shortest paths from each waypoint to the next, in the order given.
+    pub fn via_route(&self, waypoints: &[NodeId]) -> Result<Tour, GraphError> {
+        let costs = tsp::Costs::new(self, waypoints)?;
+        let order: Vec<usize> = (0..waypoints.len()).collect();
+
+        costs.tour(&order, false)
+    }
+
+    // Like via_route, but visits the waypoints between the first and last
+    // in whichever order is cheapest overall. Exact up to
+    // tsp::HELD_KARP_LIMIT waypoints, and a 2-opt improved nearest
+    // neighbour route beyond that.
+    pub fn via_route_reordered(&self, waypoints: &[NodeId]) -> Result<Tour, GraphError> {
+        let costs = tsp::Costs::new(self, waypoints)?;
+        let order = if waypoints.len() <= tsp::HELD_KARP_LIMIT {
+            tsp::held_karp(&costs, false)?
+        } else {
+            tsp::two_opt(&costs, tsp::nearest_neighbour(&costs, false), false)
+        };
+
+        costs.tour(&order, false)
+    }
+
+    pub fn shortest_path_tree(&self, start: NodeId) -> Result<ShortestPathTree, GraphError> {
+        ShortestPathTree::new(self, start)
+    }
+
+    pub fn floyd_warshall(&self) -> Result<DistanceMatrix, GraphError> {
+        DistanceMatrix::floyd_warshall(self)
+    }
+
+    pub fn all_pairs_shortest_paths(&self) -> DistanceMatrix {
+        DistanceMatrix::repeated_dijkstra(self)
+    }
+
+    pub fn astar(
+        &self,
+        start: NodeId,
+        end: NodeId,
+    ) -> Result<Option<Vec<(NodeId, f64)>>, GraphError> {
+        self.astar_with(start, end, self.metric)
+    }
+
+    pub fn astar_with<H: astar::Heuristic>(
+        &self,
+        start: NodeId,
+        end: NodeId,
+        heuristic: H,
+    ) -> Result<Option<Vec<(NodeId, f64)>>, GraphError> {
+        let mut search = astar::AStar::with_heuristic(self, start, end, heuristic)?;
 
         while let Status::Searching = search.next() {}
 
         Ok(search.result())
     }
 
-    pub fn step_shortest_path(&self, start: &str, end: &str) -> dijk::ShortestPath {
-        dijk::ShortestPath::new(self, start, end)
+    pub fn step_astar(&self, start: NodeId, end: NodeId) -> Result<astar::AStar<'_>, GraphError> {
+        astar::AStar::new(self, start, end)
+    }
+
+    pub fn step_astar_with<H: astar::Heuristic>(
+        &self,
+        start: NodeId,
+        end: NodeId,
+        heuristic: H,
+    ) -> Result<astar::AStar<'_, H>, GraphError> {
+        astar::AStar::with_heuristic(self, start, end, heuristic)
+    }
+
+    pub fn greedy_best_first_search(
+        &self,
+        start: NodeId,
+        end: NodeId,
+    ) -> Result<Option<Vec<(NodeId, f64)>>, GraphError> {
+        let mut search = greedy::GreedyBestFirst::new(self, start, end)?;
+
+        while let Status::Searching = search.next() {}
+
+        Ok(search.result())
+    }
+
+    pub fn step_greedy_best_first_search(
+        &self,
+        start: NodeId,
+        end: NodeId,
+    ) -> Result<greedy::GreedyBestFirst<'_>, GraphError> {
+        greedy::GreedyBestFirst::new(self, start, end)
+    }
+
+    pub fn beam_search(
+        &self,
+        start: NodeId,
+        end: NodeId,
+        width: usize,
+    ) -> Result<Option<Vec<(NodeId, f64)>>, GraphError> {
+        let mut search = beam::BeamSearch::new(self, start, end, width)?;
+
+        while let Status::Searching = search.next() {}
+
+        Ok(search.result())
+    }
+
+    pub fn step_beam_search(
+        &self,
+        start: NodeId,
+        end: NodeId,
+        width: usize,
+    ) -> Result<beam::BeamSearch<'_>, GraphError> {
+        beam::BeamSearch::new(self, start, end, width)
+    }
+}
+
+impl Index<NodeId> for Graph {
+    type Output = Node;
+
+    fn index(&self, id: NodeId) -> &Node {
+        self.nodes[id.index()]
+            .as_ref()
+            .expect("graph should contain node")
     }
 }
 
 // Private methods
 impl Graph {
-    fn calc_dist(&self, id_one: &str, id_two: &str) -> f64 {
-        let one = &self.nodes[id_one];
-        let two = &self.nodes[id_two];
+    fn calc_dist(&self, id_one: NodeId, id_two: NodeId) -> Result<f64, GraphError> {
+        let one = self.node(id_one)?;
+        let two = self.node(id_two)?;
+
+        if id_one == id_two {
+            return Err(GraphError::SelfLoop(self.name(id_one)?.to_owned()));
+        }
 
-        one.point().dist(&two.point())
+        let dist = self.metric.dist(one.point(), two.point());
+        if dist.is_nan() {
+            return Err(GraphError::NanDistance(
+                self.name(id_one)?.to_owned(),
+                self.name(id_two)?.to_owned(),
+            ));
+        }
+        if dist.is_infinite() {
+            return Err(GraphError::InvalidWeight(dist));
+        }
+
+        Ok(dist)
     }
-}
 
-#[derive(Debug)]
-pub struct DoesNotContainError;
+    fn check_edge(&self, id_one: NodeId, id_two: NodeId, weight: f64) -> Result<(), GraphError> {
+        self.node(id_one)?;
+        self.node(id_two)?;
+
+        if id_one == id_two {
+            return Err(GraphError::SelfLoop(self.name(id_one)?.to_owned()));
+        }
+        if !weight.is_finite() {
+            return Err(GraphError::InvalidWeight(weight));
+        }
 
-impl fmt::Display for DoesNotContainError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Graph does not contain that node.")
+        Ok(())
+    }
+
+    fn check_capacity(
+        &self,
+        id: NodeId,
+        other_id: NodeId,
+        capacity: f64,
+    ) -> Result<(), GraphError> {
+        self.node(id)?;
+        self.node(other_id)?;
+
+        if self.weight(id, other_id).is_none() {
+            return Err(GraphError::UnknownEdge(id, other_id));
+        }
+        if !capacity.is_finite() || capacity < 0.0 {
+            return Err(GraphError::InvalidCapacity(capacity));
+        }
+
+        Ok(())
     }
 }
 
-impl Error for DoesNotContainError {}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_unknown_and_duplicate_nodes() {
+        let mut graph = Graph::new();
+        let a = graph.add_node("a", Point::new(0.0, 0.0)).unwrap();
+
+        assert_eq!(graph.id("b"), Err(GraphError::UnknownNode("b".to_owned())));
+        assert_eq!(
+            graph.add_node("a", Point::new(1.0, 1.0)),
+            Err(GraphError::DuplicateNode("a".to_owned()))
+        );
+        assert_eq!(
+            graph.add_edge(a, a),
+            Err(GraphError::SelfLoop("a".to_owned()))
+        );
+        assert!(matches!(
+            graph.add_node("c", Point::new(f64::NAN, 0.0)),
+            Err(GraphError::InvalidPoint(_, _))
+        ));
+        assert_eq!(graph.len(), 1);
+    }
+
+    #[test]
+    fn removed_ids_stay_unknown() {
+        let mut graph = Graph::new();
+        let a = graph.add_node("a", Point::new(0.0, 0.0)).unwrap();
+        let b = graph.add_node("b", Point::new(1.0, 1.0)).unwrap();
+        graph.add_edge(a, b).unwrap();
+
+        graph.remove_node(a).unwrap();
+        assert_eq!(graph.node(a).err(), Some(GraphError::UnknownId(a)));
+        assert_eq!(graph.name(a), Err(GraphError::UnknownId(a)));
+        assert_eq!(graph.add_edge(a, b), Err(GraphError::UnknownId(a)));
+        assert_eq!(graph.edges(b).count(), 0);
+
+        // The name is free again, under a new id.
+        let again = graph.add_node("a", Point::new(0.0, 0.0)).unwrap();
+        assert_ne!(again, a);
+        assert_eq!(graph.node_bound(), 3);
+    }
+
+    #[test]
+    fn errors_read_as_sentences() {
+        assert_eq!(
+            GraphError::UnknownNode("Nowhere".to_owned()).to_string(),
+            "Graph does not contain node \"Nowhere\"."
+        );
+        assert_eq!(
+            GraphError::UnknownId(NodeId::new(3)).to_string(),
+            "Graph does not contain node #3."
+        );
+    }
+
+    #[test]
+    fn weighted_edges_ignore_coordinates() {
+        let mut graph = Graph::new();
+        let a = graph.add_node("a", Point::new(0.0, 0.0)).unwrap();
+        let b = graph.add_node("b", Point::new(3.0, 4.0)).unwrap();
+
+        graph.add_edge(a, b).unwrap();
+        assert_eq!(graph.weight(a, b), Some(5.0));
+        graph.add_weighted_edge(a, b, 1.5).unwrap();
+        assert_eq!(graph.weight(a, b), Some(1.5));
+        assert_eq!(graph.weight(b, a), Some(1.5));
+        graph.add_directed_weighted_edge(b, a, 7.0).unwrap();
+        assert_eq!(graph.weight(a, b), Some(1.5));
+        assert_eq!(graph.weight(b, a), Some(7.0));
+    }
+
+    #[test]
+    fn rejects_bad_weights() {
+        let mut graph = Graph::new();
+        let a = graph.add_node("a", Point::new(0.0, 0.0)).unwrap();
+        let b = graph.add_node("b", Point::new(1.0, 1.0)).unwrap();
+
+        assert_eq!(
+            graph.add_weighted_edge(a, b, -1.0),
+            Err(GraphError::NegativeWeight(-1.0))
+        );
+        assert_eq!(
+            graph.add_weighted_edge(a, b, f64::INFINITY),
+            Err(GraphError::InvalidWeight(f64::INFINITY))
+        );
+        assert!(matches!(
+            graph.add_directed_weighted_edge(a, b, f64::NAN),
+            Err(GraphError::InvalidWeight(_))
+        ));
+        assert_eq!(graph.weight(a, b), None);
+
+        // One way, a negative weight can't loop straight back.
+        graph.add_directed_weighted_edge(a, b, -1.0).unwrap();
+        assert_eq!(graph.weight(a, b), Some(-1.0));
+    }
+
+    #[test]
+    fn frozen_graphs_refuse_changes_until_thawed() {
+        let mut graph = Graph::new();
+        let a = graph.add_node("a", Point::new(0.0, 0.0)).unwrap();
+        let b = graph.add_node("b", Point::new(3.0, 4.0)).unwrap();
+        graph.add_edge(a, b).unwrap();
+
+        graph.freeze();
+        assert_eq!(graph.weight(a, b), Some(5.0));
+        assert_eq!(graph.shortest_path(a, b).unwrap().unwrap().len(), 2);
+        assert_eq!(graph.add_directed_edge(b, a), Err(GraphError::Frozen));
+        assert_eq!(graph.remove_edge(a, b), Err(GraphError::Frozen));
+        assert_eq!(
+            graph.add_node("c", Point::new(0.0, 0.0)),
+            Err(GraphError::Frozen)
+        );
+        assert_eq!(graph.len(), 2);
+
+        graph.set_storage::<storage::list::AdjacencyList>().unwrap();
+        assert_eq!(graph.remove_edge(a, b), Ok(Some(5.0)));
+        assert_eq!(graph.weight(b, a), Some(5.0));
+    }
+}
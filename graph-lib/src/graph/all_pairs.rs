@@ -0,0 +1,293 @@
+use std::io::{self, Write};
+use std::ops::Index;
+
+use crate::graph::node::NodeId;
+use crate::graph::tree::dijkstra;
+use crate::graph::{Graph, GraphError};
+
+// Shortest distances between every pair of nodes, indexed by
+// (from, to). Also remembers enough to rebuild each path.
+#[derive(Debug, Clone)]
+pub struct DistanceMatrix {
+    ids: Vec<NodeId>,
+    dist: Vec<Vec<f64>>,
+    // prev[from][to] is the node before `to` on the path from `from`.
+    prev: Vec<Vec<Option<NodeId>>>,
+}
+
+// Associate functions
+impl DistanceMatrix {
+    // O(n³), but with no per-edge overhead, so it suits dense graphs.
+    // Negative weights are fine, but a negative cycle leaves some pairs
+    // with no shortest path at all, so that's an error.
+    pub fn floyd_warshall(graph: &Graph) -> Result<DistanceMatrix, GraphError> {
+        let mut matrix = DistanceMatrix::empty(graph);
+        let size = graph.node_bound();
+
+        for node in graph.nodes() {
+            let from = node.id().index();
+            for (to, weight) in graph.edges(node.id()) {
+                if weight < matrix.dist[from][to.index()] {
+                    matrix.dist[from][to.index()] = weight;
+                    matrix.prev[from][to.index()] = Some(node.id());
+                }
+            }
+        }
+
+        for k in 0..size {
+            for i in 0..size {
+                if matrix.dist[i][k].is_infinite() {
+                    continue;
+                }
+                for j in 0..size {
+                    let through = matrix.dist[i][k] + matrix.dist[k][j];
+                    if through < matrix.dist[i][j] {
+                        matrix.dist[i][j] = through;
+                        matrix.prev[i][j] = matrix.prev[k][j];
+                    }
+                }
+            }
+
+            // A node on a negative cycle can get back to itself for less
+            // than nothing. Stop as soon as one does, while each row's
+            // predecessors still lead back along the cycle.
+            let negative = matrix
+                .ids
+                .iter()
+                .find(|id| matrix.dist[id.index()][id.index()] < 0.0);
+            if let Some(id) = negative {
+                return Err(GraphError::NegativeCycle(matrix.cycle_from(*id)));
+            }
+        }
+
+        Ok(matrix)
+    }
+
+    // One Dijkstra run per node, which is quicker on sparse graphs.
+    pub fn repeated_dijkstra(graph: &Graph) -> DistanceMatrix {
+        let mut matrix = DistanceMatrix::empty(graph);
+
+        for node in graph.nodes() {
+            let from = node.id().index();
+            for (to, (prev, dist)) in dijkstra(graph, node.id()) {
+                matrix.dist[from][to.index()] = dist;
+                matrix.prev[from][to.index()] = prev;
+            }
+        }
+
+        matrix
+    }
+}
+
+// Public methods
+impl DistanceMatrix {
+    // The nodes in the matrix, in id order.
+    pub fn ids(&self) -> &[NodeId] {
+        &self.ids
+    }
+
+    pub fn distance(&self, from: NodeId, to: NodeId) -> Option<f64> {
+        let dist = *self.dist.get(from.index())?.get(to.index())?;
+
+        if dist.is_infinite() {
+            None
+        } else {
+            Some(dist)
+        }
+    }
+
+    // The same shape as a search result: each node on the path with the
+    // distance travelled to reach it.
+    pub fn path(&self, from: NodeId, to: NodeId) -> Option<Vec<(NodeId, f64)>> {
+        self.distance(from, to)?;
+
+        let row = &self.dist[from.index()];
+        let mut path = vec![(to, row[to.index()])];
+        let mut id = to;
+        while id != from {
+            // A shortest path never visits a node twice.
+            if path.len() > self.ids.len() {
+                return None;
+            }
+            id = self.prev[from.index()][id.index()]?;
+            path.push((id, row[id.index()]));
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    // A header row of node names, then one row per node. Unreachable
+    // pairs are left empty.
+    pub fn write_csv<W: Write>(&self, graph: &Graph, mut writer: W) -> io::Result<()> {
+        let names: Vec<String> = self
+            .ids
+            .iter()
+            .map(|id| csv_field(graph.name(*id).unwrap_or("")))
+            .collect();
+
+        writeln!(writer, ",{}", names.join(","))?;
+        for (from, name) in self.ids.iter().zip(names.iter()) {
+            let row: Vec<String> = self
+                .ids
+                .iter()
+                .map(|to| match self.distance(*from, *to) {
+                    Some(dist) => dist.to_string(),
+                    None => String::new(),
+                })
+                .collect();
+            writeln!(writer, "{},{}", name, row.join(","))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn to_csv(&self, graph: &Graph) -> String {
+        let mut csv = Vec::new();
+        self.write_csv(graph, &mut csv)
+            .expect("writing to a Vec should not fail");
+
+        String::from_utf8(csv).expect("csv should be valid utf-8")
+    }
+}
+
+impl Index<(NodeId, NodeId)> for DistanceMatrix {
+    type Output = f64;
+
+    // Infinity for pairs with no path between them.
+    fn index(&self, (from, to): (NodeId, NodeId)) -> &f64 {
+        &self.dist[from.index()][to.index()]
+    }
+}
+
+// Private methods
+impl DistanceMatrix {
+    // Walking back from a node along the paths out of it must repeat a
+    // node, and the stretch between the repeats is a cycle.
+    fn cycle_from(&self, from: NodeId) -> Vec<NodeId> {
+        let row = &self.prev[from.index()];
+        let mut walk = vec![from];
+        let mut id = from;
+        while let Some(prev) = row[id.index()] {
+            if let Some(start) = walk.iter().position(|id| *id == prev) {
+                let mut cycle = walk.split_off(start);
+                cycle.reverse();
+                return cycle;
+            }
+            walk.push(prev);
+            id = prev;
+        }
+
+        walk.reverse();
+        walk
+    }
+
+    fn empty(graph: &Graph) -> DistanceMatrix {
+        let size = graph.node_bound();
+        let mut dist = vec![vec![f64::INFINITY; size]; size];
+        for node in graph.nodes() {
+            dist[node.id().index()][node.id().index()] = 0.0;
+        }
+
+        DistanceMatrix {
+            ids: graph.nodes().map(|node| node.id()).collect(),
+            dist,
+            prev: vec![vec![None; size]; size],
+        }
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::point::Point;
+    use crate::graph::{Graph, GraphError};
+
+    fn cycle(weights: &[f64]) -> (Graph, Vec<crate::graph::node::NodeId>) {
+        let mut graph = Graph::new();
+        let ids: Vec<_> = (0..weights.len())
+            .map(|i| {
+                graph
+                    .add_node(&i.to_string(), Point::new(0.0, 0.0))
+                    .unwrap()
+            })
+            .collect();
+        for (i, weight) in weights.iter().enumerate() {
+            let next = ids[(i + 1) % ids.len()];
+            graph
+                .add_directed_weighted_edge(ids[i], next, *weight)
+                .unwrap();
+        }
+
+        (graph, ids)
+    }
+
+    #[test]
+    fn agrees_with_repeated_dijkstra() {
+        let (mut graph, ids) = cycle(&[1.0, 2.0, 3.0, 4.0]);
+        graph
+            .add_directed_weighted_edge(ids[0], ids[2], 2.5)
+            .unwrap();
+
+        let floyd = graph.floyd_warshall().unwrap();
+        let dijkstra = graph.all_pairs_shortest_paths();
+        for from in ids.iter() {
+            for to in ids.iter() {
+                assert_eq!(floyd.distance(*from, *to), dijkstra.distance(*from, *to));
+            }
+        }
+        assert_eq!(
+            floyd.path(ids[0], ids[3]),
+            Some(vec![(ids[0], 0.0), (ids[2], 2.5), (ids[3], 5.5)])
+        );
+    }
+
+    #[test]
+    fn reports_negative_cycle() {
+        let (graph, ids) = cycle(&[1.0, -0.5, -1.0]);
+
+        match graph.floyd_warshall() {
+            Err(GraphError::NegativeCycle(mut found)) => {
+                found.sort();
+                assert_eq!(found, ids);
+            }
+            other => panic!("expected a negative cycle, got {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn survives_cycle_that_rounds_below_zero() {
+        // 0.1 + 0.1 + 0.2 - 0.4 adds up to zero on paper only.
+        let (graph, ids) = cycle(&[0.1, 0.1, 0.2, -0.4]);
+
+        match graph.floyd_warshall() {
+            Ok(matrix) => assert!(matrix.distance(ids[0], ids[3]).is_some()),
+            Err(GraphError::NegativeCycle(found)) => assert_eq!(found.len(), 4),
+            Err(other) => panic!("expected a matrix or a cycle, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn exports_csv() {
+        let mut graph = Graph::new();
+        let a = graph.add_node("a", Point::new(0.0, 0.0)).unwrap();
+        let b = graph
+            .add_node("b, \"the\" other", Point::new(0.0, 0.0))
+            .unwrap();
+        graph.add_directed_weighted_edge(a, b, 1.5).unwrap();
+
+        let matrix = graph.all_pairs_shortest_paths();
+        assert_eq!(matrix[(b, a)], f64::INFINITY);
+        assert_eq!(
+            matrix.to_csv(&graph),
+            ",a,\"b, \"\"the\"\" other\"\na,0,1.5\n\"b, \"\"the\"\" other\",,0\n"
+        );
+    }
+}
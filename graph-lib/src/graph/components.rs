@@ -0,0 +1,344 @@
+use std::collections::VecDeque;
+
+use crate::graph::node::NodeId;
+use crate::graph::Graph;
+
+// A component id for every node in the graph, numbered from 0.
+#[derive(Debug, Clone)]
+pub struct Components {
+    ids: Vec<Option<usize>>,
+    count: usize,
+    // For strongly connected components, the other components each one
+    // has edges into. Unused for connected components, where being in the
+    // same component is the same as being reachable.
+    condensation: Option<Vec<Vec<usize>>>,
+    // One bit per component for everything each component can reach,
+    // once index_reachability has been called.
+    reachable: Option<Vec<Vec<u64>>>,
+}
+
+// Associate functions
+impl Components {
+    // Ignores edge directions, so on a directed graph these are the weakly
+    // connected components.
+    pub fn connected(graph: &Graph) -> Components {
+        let mut ids = vec![None; graph.node_bound()];
+        let mut count = 0;
+
+        for node in graph.nodes() {
+            if ids[node.id().index()].is_some() {
+                continue;
+            }
+
+            let mut queue = VecDeque::new();
+            queue.push_back(node.id());
+            ids[node.id().index()] = Some(count);
+
+            while let Some(id) = queue.pop_front() {
+                for (other_id, _) in graph.edges(id).chain(graph.incoming(id)) {
+                    if ids[other_id.index()].is_none() {
+                        ids[other_id.index()] = Some(count);
+                        queue.push_back(other_id);
+                    }
+                }
+            }
+
+            count += 1;
+        }
+
+        Components {
+            ids,
+            count,
+            condensation: None,
+            reachable: None,
+        }
+    }
+
+    // Tarjan's algorithm, without recursion so long paths can't overflow
+    // the stack. Components come out with sinks first, so every edge
+    // between two components goes from a higher id to a lower one.
+    pub fn strongly_connected(graph: &Graph) -> Components {
+        let mut tarjan = Tarjan::new(graph);
+        for node in graph.nodes() {
+            if tarjan.index[node.id().index()].is_none() {
+                tarjan.run(node.id());
+            }
+        }
+
+        let mut components = Components {
+            ids: tarjan.ids,
+            count: tarjan.count,
+            condensation: None,
+            reachable: None,
+        };
+        components.condensation = Some(components.condensation(graph));
+
+        components
+    }
+}
+
+// Public methods
+impl Components {
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn component(&self, id: NodeId) -> Option<usize> {
+        *self.ids.get(id.index())?
+    }
+
+    // The nodes in each component, indexed by component id.
+    pub fn members(&self) -> Vec<Vec<NodeId>> {
+        let mut members = vec![Vec::new(); self.count];
+        for (i, component) in self.ids.iter().enumerate() {
+            if let Some(component) = component {
+                members[*component].push(NodeId::new(i));
+            }
+        }
+
+        members
+    }
+
+    // For strongly connected components, whether there's a path from a to
+    // b. Without index_reachability that means searching the components
+    // between theirs, so it's quick for nodes in the same component and
+    // up to O(V + E) of the condensation the further apart they are.
+    // Connected components ignore edge directions, so there it's whether a
+    // and b are joined at all, either way round. False if either node isn't
+    // in the graph.
+    pub fn is_reachable(&self, a: NodeId, b: NodeId) -> bool {
+        let (a, b) = match (self.component(a), self.component(b)) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return false,
+        };
+
+        match (&self.reachable, &self.condensation) {
+            (Some(reachable), _) => reachable[a][b / 64] & (1 << (b % 64)) != 0,
+            (None, Some(condensation)) => reaches(condensation, a, b),
+            (None, None) => a == b,
+        }
+    }
+
+    // Works out up front everything each strongly connected component can
+    // reach, so is_reachable answers in O(1) from then on. Takes a bit per
+    // pair of components, so it's worth it when asking many times.
+    pub fn index_reachability(&mut self) {
+        let condensation = match (&self.reachable, &self.condensation) {
+            (None, Some(condensation)) => condensation,
+            _ => return,
+        };
+
+        // Edges only lead to lower ids, so going up from the sinks, every
+        // component a component leads to is already done.
+        let words = self.count.div_ceil(64);
+        let mut reachable = vec![vec![0u64; words]; self.count];
+        for component in 0..self.count {
+            let (done, rest) = reachable.split_at_mut(component);
+            let bits = &mut rest[0];
+            bits[component / 64] |= 1 << (component % 64);
+            for other in condensation[component].iter() {
+                for (bit, other_bit) in bits.iter_mut().zip(done[*other].iter()) {
+                    *bit |= other_bit;
+                }
+            }
+        }
+
+        self.reachable = Some(reachable);
+    }
+}
+
+// Private methods
+impl Components {
+    // The graph with each component squashed into a single node, which
+    // has no cycles.
+    fn condensation(&self, graph: &Graph) -> Vec<Vec<usize>> {
+        let mut condensation = vec![Vec::new(); self.count];
+
+        for node in graph.nodes() {
+            let component = self.ids[node.id().index()].unwrap();
+            for (other_id, _) in graph.edges(node.id()) {
+                let other = self.ids[other_id.index()].unwrap();
+                if other != component {
+                    condensation[component].push(other);
+                }
+            }
+        }
+
+        for others in condensation.iter_mut() {
+            others.sort_unstable();
+            others.dedup();
+        }
+
+        condensation
+    }
+}
+
+// Depth first search from one component towards another. Edges between
+// components only ever lead to lower ids, so anything below the target
+// is a dead end and only the components in between need remembering.
+fn reaches(condensation: &[Vec<usize>], from: usize, to: usize) -> bool {
+    if from <= to {
+        return from == to;
+    }
+
+    let mut seen = vec![false; from - to];
+    let mut stack = vec![from];
+    while let Some(component) = stack.pop() {
+        for other in condensation[component].iter().copied() {
+            if other == to {
+                return true;
+            }
+            if other > to && !seen[other - to - 1] {
+                seen[other - to - 1] = true;
+                stack.push(other);
+            }
+        }
+    }
+
+    false
+}
+
+struct Tarjan<'a> {
+    graph: &'a Graph,
+    ids: Vec<Option<usize>>,
+    count: usize,
+    index: Vec<Option<usize>>,
+    low: Vec<usize>,
+    next_index: usize,
+    on_stack: Vec<bool>,
+    stack: Vec<NodeId>,
+    // Stands in for the call stack: each node being explored, its
+    // neighbours and how many of them have been looked at.
+    work: Vec<(NodeId, Vec<NodeId>, usize)>,
+}
+
+impl<'a> Tarjan<'a> {
+    fn new(graph: &'a Graph) -> Tarjan<'a> {
+        let size = graph.node_bound();
+
+        Tarjan {
+            graph,
+            ids: vec![None; size],
+            count: 0,
+            index: vec![None; size],
+            low: vec![0; size],
+            next_index: 0,
+            on_stack: vec![false; size],
+            stack: Vec::new(),
+            work: Vec::new(),
+        }
+    }
+
+    fn run(&mut self, start: NodeId) {
+        self.visit(start);
+
+        while let Some((id, edges, position)) = self.work.last_mut() {
+            let id = *id;
+
+            if *position < edges.len() {
+                let other_id = edges[*position];
+                *position += 1;
+
+                match self.index[other_id.index()] {
+                    None => self.visit(other_id),
+                    Some(other_index) if self.on_stack[other_id.index()] => {
+                        self.low[id.index()] = self.low[id.index()].min(other_index);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            self.work.pop();
+            if let Some((parent, _, _)) = self.work.last() {
+                self.low[parent.index()] = self.low[parent.index()].min(self.low[id.index()]);
+            }
+
+            if Some(self.low[id.index()]) == self.index[id.index()] {
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack[member.index()] = false;
+                    self.ids[member.index()] = Some(self.count);
+                    if member == id {
+                        break;
+                    }
+                }
+                self.count += 1;
+            }
+        }
+    }
+
+    fn visit(&mut self, id: NodeId) {
+        self.index[id.index()] = Some(self.next_index);
+        self.low[id.index()] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(id);
+        self.on_stack[id.index()] = true;
+
+        let edges = self.graph.edges(id).map(|(id, _)| id).collect();
+        self.work.push((id, edges, 0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::point::Point;
+    use crate::graph::Graph;
+
+    // Two cycles, a -> b -> a and c -> d -> e -> c, joined one way by b -> c,
+    // and f off on its own.
+    fn two_cycles() -> (Graph, Vec<crate::graph::node::NodeId>) {
+        let mut graph = Graph::new();
+        let ids: Vec<_> = ["a", "b", "c", "d", "e", "f"]
+            .iter()
+            .map(|name| graph.add_node(name, Point::new(0.0, 0.0)).unwrap())
+            .collect();
+        for (from, to) in [(0, 1), (1, 0), (1, 2), (2, 3), (3, 4), (4, 2)].iter() {
+            graph.add_directed_edge(ids[*from], ids[*to]).unwrap();
+        }
+
+        (graph, ids)
+    }
+
+    #[test]
+    fn strongly_connected_groups_cycles() {
+        let (graph, ids) = two_cycles();
+        let components = graph.strongly_connected_components();
+
+        assert_eq!(components.count(), 3);
+        assert_eq!(components.component(ids[0]), components.component(ids[1]));
+        assert_eq!(components.component(ids[2]), components.component(ids[4]));
+        assert_ne!(components.component(ids[0]), components.component(ids[2]));
+        // Sinks come first, so edges between components go downwards.
+        assert!(components.component(ids[0]) > components.component(ids[2]));
+    }
+
+    #[test]
+    fn connected_ignores_directions() {
+        let (graph, ids) = two_cycles();
+        let components = graph.connected_components();
+
+        assert_eq!(components.count(), 2);
+        assert!(components.is_reachable(ids[4], ids[0]));
+        assert!(!components.is_reachable(ids[0], ids[5]));
+    }
+
+    #[test]
+    fn indexed_reachability_matches_search() {
+        let (graph, ids) = two_cycles();
+        let components = graph.strongly_connected_components();
+        let mut indexed = components.clone();
+        indexed.index_reachability();
+
+        for a in ids.iter() {
+            for b in ids.iter() {
+                assert_eq!(
+                    indexed.is_reachable(*a, *b),
+                    components.is_reachable(*a, *b)
+                );
+            }
+        }
+        assert!(indexed.is_reachable(ids[0], ids[4]));
+        assert!(!indexed.is_reachable(ids[4], ids[0]));
+        assert!(!indexed.is_reachable(ids[5], ids[0]));
+    }
+}
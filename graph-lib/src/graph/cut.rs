@@ -0,0 +1,172 @@
+use std::collections::BTreeSet;
+
+use crate::graph::node::NodeId;
+use crate::graph::Graph;
+
+// Nodes and edges whose removal would split the graph apart. Edge
+// directions are ignored, and each bridge is given smaller id first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Cuts {
+    articulation_points: BTreeSet<NodeId>,
+    bridges: BTreeSet<(NodeId, NodeId)>,
+}
+
+// Public methods
+impl Cuts {
+    pub fn articulation_points(&self) -> &BTreeSet<NodeId> {
+        &self.articulation_points
+    }
+
+    pub fn bridges(&self) -> &BTreeSet<(NodeId, NodeId)> {
+        &self.bridges
+    }
+}
+
+// Hopcroft and Tarjan's depth first search, without recursion. A child
+// whose subtree has no edge reaching above its parent is cut off by
+// removing the parent, and if it can't reach the parent itself either,
+// by removing the edge between them.
+pub fn find(graph: &Graph) -> Cuts {
+    let size = graph.node_bound();
+    let mut discovered: Vec<Option<usize>> = vec![None; size];
+    let mut low = vec![0; size];
+    let mut time = 0;
+    let mut cuts = Cuts::default();
+
+    for root in graph.nodes().map(|node| node.id()) {
+        if discovered[root.index()].is_some() {
+            continue;
+        }
+
+        let mut root_children = 0;
+        // Each node being explored, the node it was reached from, its
+        // neighbours and how many of them have been looked at.
+        let mut stack = vec![(root, None, neighbours(graph, root), 0)];
+        discovered[root.index()] = Some(time);
+        low[root.index()] = time;
+        time += 1;
+
+        while let Some((id, parent, edges, position)) = stack.last_mut() {
+            let (id, parent) = (*id, *parent);
+
+            if *position < edges.len() {
+                let other_id = edges[*position];
+                *position += 1;
+
+                if Some(other_id) == parent {
+                    continue;
+                }
+                match discovered[other_id.index()] {
+                    Some(other) => low[id.index()] = low[id.index()].min(other),
+                    None => {
+                        if id == root {
+                            root_children += 1;
+                        }
+                        discovered[other_id.index()] = Some(time);
+                        low[other_id.index()] = time;
+                        time += 1;
+                        stack.push((other_id, Some(id), neighbours(graph, other_id), 0));
+                    }
+                }
+                continue;
+            }
+
+            stack.pop();
+            let parent = match parent {
+                Some(parent) => parent,
+                None => continue,
+            };
+
+            low[parent.index()] = low[parent.index()].min(low[id.index()]);
+            let parent_discovered = discovered[parent.index()].unwrap();
+            if low[id.index()] > parent_discovered {
+                cuts.bridges.insert((parent.min(id), parent.max(id)));
+            }
+            if parent != root && low[id.index()] >= parent_discovered {
+                cuts.articulation_points.insert(parent);
+            }
+        }
+
+        if root_children > 1 {
+            cuts.articulation_points.insert(root);
+        }
+    }
+
+    cuts
+}
+
+// Each neighbour once, whichever way the edges between them go.
+fn neighbours(graph: &Graph, id: NodeId) -> Vec<NodeId> {
+    let all: BTreeSet<NodeId> = graph
+        .edges(id)
+        .chain(graph.incoming(id))
+        .map(|(id, _)| id)
+        .collect();
+
+    all.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::graph::node::NodeId;
+    use crate::graph::point::Point;
+    use crate::graph::Graph;
+
+    // A triangle a b c, a tail c - d - e, and a second triangle e f g
+    // hanging off e, with one of its edges only going one way.
+    fn bowtie() -> (Graph, Vec<NodeId>) {
+        let mut graph = Graph::new();
+        let ids: Vec<_> = ["a", "b", "c", "d", "e", "f", "g"]
+            .iter()
+            .map(|name| graph.add_node(name, Point::new(0.0, 0.0)).unwrap())
+            .collect();
+        for (from, to) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 6)].iter() {
+            graph.add_weighted_edge(ids[*from], ids[*to], 1.0).unwrap();
+        }
+        graph
+            .add_directed_weighted_edge(ids[6], ids[4], 1.0)
+            .unwrap();
+
+        (graph, ids)
+    }
+
+    #[test]
+    fn finds_the_known_cuts() {
+        let (graph, ids) = bowtie();
+        let cuts = graph.cuts();
+
+        let points: BTreeSet<NodeId> = [ids[2], ids[3], ids[4]].iter().copied().collect();
+        let bridges: BTreeSet<(NodeId, NodeId)> = [(ids[2], ids[3]), (ids[3], ids[4])]
+            .iter()
+            .copied()
+            .collect();
+        assert_eq!(cuts.articulation_points(), &points);
+        assert_eq!(cuts.bridges(), &bridges);
+    }
+
+    #[test]
+    fn matches_removing_each_node_and_edge() {
+        let (graph, ids) = bowtie();
+        let cuts = graph.cuts();
+        let count = graph.connected_components().count();
+
+        for id in ids.iter() {
+            let mut without = graph.clone();
+            without.remove_node(*id).unwrap();
+            let split = without.connected_components().count() > count;
+            assert_eq!(cuts.articulation_points().contains(id), split);
+        }
+        for id in ids.iter() {
+            for (other_id, _) in graph.edges(*id) {
+                let mut without = graph.clone();
+                without.remove_edge(*id, other_id).unwrap();
+                let _ = without.remove_edge(other_id, *id);
+                let split = without.connected_components().count() > count;
+                let edge = (*id.min(&other_id), *id.max(&other_id));
+                assert_eq!(cuts.bridges().contains(&edge), split);
+            }
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use std::collections::BTreeSet;
+
+use crate::graph::node::NodeId;
+use crate::graph::Graph;
+
+// Cycles come back as their nodes in the order they're visited, without
+// repeating the first one at the end.
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Mark {
+    New,
+    // Still on the depth first search's stack.
+    Open,
+    Done,
+}
+
+// Following edge directions, so an edge added with add_edge is already a
+// cycle of two nodes.
+pub fn find_directed(graph: &Graph) -> Option<Vec<NodeId>> {
+    find(
+        graph,
+        |id| graph.edges(id).map(|(id, _)| id).collect(),
+        false,
+    )
+}
+
+// Ignoring edge directions. Edges both ways between two nodes count as one
+// link, so a cycle here has at least three nodes.
+pub fn find_undirected(graph: &Graph) -> Option<Vec<NodeId>> {
+    let neighbours = |id| {
+        let all: BTreeSet<NodeId> = graph
+            .edges(id)
+            .chain(graph.incoming(id))
+            .map(|(id, _)| id)
+            .collect();
+
+        all.into_iter().collect()
+    };
+
+    find(graph, neighbours, true)
+}
+
+// Depth first search without recursion. An edge back to a node still on
+// the stack closes a cycle through everything stacked after it. Undirected,
+// the link straight back to where a node was reached from doesn't count.
+fn find<F>(graph: &Graph, neighbours: F, undirected: bool) -> Option<Vec<NodeId>>
+where
+    F: Fn(NodeId) -> Vec<NodeId>,
+{
+    let mut marks = vec![Mark::New; graph.node_bound()];
+
+    for root in graph.nodes().map(|node| node.id()) {
+        if marks[root.index()] != Mark::New {
+            continue;
+        }
+
+        // Each node being explored, its neighbours and how many of them
+        // have been looked at.
+        let mut stack = vec![(root, neighbours(root), 0)];
+        marks[root.index()] = Mark::Open;
+
+        while let Some((id, edges, position)) = stack.last_mut() {
+            let id = *id;
+            if *position == edges.len() {
+                marks[id.index()] = Mark::Done;
+                stack.pop();
+                continue;
+            }
+
+            let other_id = edges[*position];
+            *position += 1;
+
+            match marks[other_id.index()] {
+                Mark::New => {
+                    marks[other_id.index()] = Mark::Open;
+                    stack.push((other_id, neighbours(other_id), 0));
+                }
+                Mark::Open => {
+                    let parent = stack.len().checked_sub(2).map(|i| stack[i].0);
+                    if undirected && Some(other_id) == parent {
+                        continue;
+                    }
+
+                    let start = stack.iter().position(|(id, _, _)| *id == other_id)?;
+                    return Some(stack[start..].iter().map(|(id, _, _)| *id).collect());
+                }
+                Mark::Done => {}
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::point::Point;
+    use crate::graph::Graph;
+
+    fn nodes(len: usize) -> (Graph, Vec<crate::graph::node::NodeId>) {
+        let mut graph = Graph::new();
+        let ids = (0..len)
+            .map(|i| {
+                graph
+                    .add_node(&i.to_string(), Point::new(0.0, 0.0))
+                    .unwrap()
+            })
+            .collect();
+
+        (graph, ids)
+    }
+
+    #[test]
+    fn directed_cycle_follows_edges() {
+        let (mut graph, ids) = nodes(4);
+        for (from, to) in [(0, 1), (1, 2), (2, 3), (3, 1)].iter() {
+            graph
+                .add_directed_weighted_edge(ids[*from], ids[*to], 1.0)
+                .unwrap();
+        }
+
+        let cycle = graph.find_cycle().unwrap();
+        assert_eq!(cycle.len(), 3);
+        for (i, id) in cycle.iter().enumerate() {
+            let next = cycle[(i + 1) % cycle.len()];
+            assert!(graph.weight(*id, next).is_some());
+        }
+
+        graph.remove_edge(ids[3], ids[1]).unwrap();
+        assert_eq!(graph.find_cycle(), None);
+    }
+
+    #[test]
+    fn undirected_cycle_needs_three_nodes() {
+        let (mut graph, ids) = nodes(3);
+        graph.add_weighted_edge(ids[0], ids[1], 1.0).unwrap();
+        graph.add_weighted_edge(ids[1], ids[2], 1.0).unwrap();
+
+        // Each two-way edge is a directed cycle, but not an undirected one.
+        assert!(graph.find_cycle().is_some());
+        assert_eq!(graph.find_undirected_cycle(), None);
+
+        graph.add_weighted_edge(ids[2], ids[0], 1.0).unwrap();
+        assert_eq!(graph.find_undirected_cycle().unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,101 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::graph::node::NodeId;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphError {
+    UnknownNode(String),
+    UnknownId(NodeId),
+    DuplicateNode(String),
+    SelfLoop(String),
+    InvalidPoint(f64, f64),
+    InvalidWeight(f64),
+    NegativeWeight(f64),
+    NanDistance(String, String),
+    Frozen,
+    NegativeCycle(Vec<NodeId>),
+    Cycle(Vec<NodeId>),
+    NoPath(NodeId, NodeId),
+    TooManyStops(usize),
+    UnknownEdge(NodeId, NodeId),
+    InvalidCapacity(f64),
+    SourceIsSink(NodeId),
+    InvalidDamping(f64),
+    InvalidTolerance(f64),
+    NoConvergence(usize),
+    InvalidWidth(usize),
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GraphError::UnknownNode(id) => write!(f, "Graph does not contain node \"{}\".", id),
+            GraphError::UnknownId(id) => write!(f, "Graph does not contain node {}.", id),
+            GraphError::DuplicateNode(id) => write!(f, "Graph already contains node \"{}\".", id),
+            GraphError::SelfLoop(id) => write!(f, "Node \"{}\" cannot have an edge to itself.", id),
+            GraphError::InvalidPoint(row, col) => {
+                write!(f, "Point ({}, {}) is not made of finite numbers.", row, col)
+            }
+            GraphError::InvalidWeight(weight) => {
+                write!(f, "Edge weight {} is not a finite number.", weight)
+            }
+            GraphError::NegativeWeight(weight) => {
+                write!(f, "Undirected edge weight {} cannot be negative.", weight)
+            }
+            GraphError::NanDistance(id, other_id) => write!(
+                f,
+                "Distance between \"{}\" and \"{}\" is not a number.",
+                id, other_id
+            ),
+            GraphError::Frozen => write!(f, "Graph is frozen and cannot be changed."),
+            GraphError::NoPath(id, other_id) => {
+                write!(f, "There is no path from node {} to node {}.", id, other_id)
+            }
+            GraphError::TooManyStops(stops) => write!(
+                f,
+                "{} stops is too many to plan an exact tour through.",
+                stops
+            ),
+            GraphError::UnknownEdge(id, other_id) => write!(
+                f,
+                "Graph does not contain an edge from node {} to node {}.",
+                id, other_id
+            ),
+            GraphError::InvalidCapacity(capacity) => write!(
+                f,
+                "Edge capacity {} is not a finite, non-negative number.",
+                capacity
+            ),
+            GraphError::SourceIsSink(id) => {
+                write!(f, "Node {} cannot be both the source and the sink.", id)
+            }
+            GraphError::InvalidDamping(damping) => {
+                write!(f, "Damping factor {} is not between 0 and 1.", damping)
+            }
+            GraphError::InvalidTolerance(tolerance) => {
+                write!(f, "Tolerance {} is not a positive number.", tolerance)
+            }
+            GraphError::NoConvergence(iterations) => {
+                write!(
+                    f,
+                    "Scores did not converge within {} iterations.",
+                    iterations
+                )
+            }
+            GraphError::InvalidWidth(width) => {
+                write!(f, "Beam width {} must be at least 1.", width)
+            }
+            GraphError::NegativeCycle(cycle) => {
+                let cycle: Vec<String> = cycle.iter().map(|id| id.to_string()).collect();
+                write!(f, "Graph has a negative cycle: {}.", cycle.join(" -> "))
+            }
+            GraphError::Cycle(cycle) => {
+                let cycle: Vec<String> = cycle.iter().map(|id| id.to_string()).collect();
+                write!(f, "Graph has a cycle: {}.", cycle.join(" -> "))
+            }
+        }
+    }
+}
+
+impl Error for GraphError {}
@@ -0,0 +1,284 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use crate::graph::node::NodeId;
+use crate::graph::{Graph, GraphError};
+
+// The most that can flow from a source to a sink through the edges'
+// capacities, how much goes along each edge, and a minimum cut: the nodes
+// still reachable from the source once the flow is running. The edges
+// leaving that side are full, and their capacities add up to the flow.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaxFlow {
+    source: NodeId,
+    sink: NodeId,
+    value: f64,
+    flows: BTreeMap<(NodeId, NodeId), f64>,
+    source_side: BTreeSet<NodeId>,
+    sink_side: BTreeSet<NodeId>,
+    cut_edges: Vec<(NodeId, NodeId)>,
+}
+
+// Public methods
+impl MaxFlow {
+    pub fn source(&self) -> NodeId {
+        self.source
+    }
+
+    pub fn sink(&self) -> NodeId {
+        self.sink
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    // Only edges that carry some flow. Flow never runs both ways between
+    // two nodes.
+    pub fn flows(&self) -> &BTreeMap<(NodeId, NodeId), f64> {
+        &self.flows
+    }
+
+    pub fn flow(&self, id: NodeId, other_id: NodeId) -> f64 {
+        self.flows.get(&(id, other_id)).copied().unwrap_or(0.0)
+    }
+
+    pub fn source_side(&self) -> &BTreeSet<NodeId> {
+        &self.source_side
+    }
+
+    pub fn sink_side(&self) -> &BTreeSet<NodeId> {
+        &self.sink_side
+    }
+
+    // The edges with a capacity that cross from the source side to the
+    // sink side.
+    pub fn cut_edges(&self) -> &[(NodeId, NodeId)] {
+        &self.cut_edges
+    }
+}
+
+// Edmonds and Karp's version of Ford-Fulkerson: keep pushing as much as
+// fits along the path with fewest edges that still has room, until there
+// are none. Edges with no capacity set carry nothing.
+pub fn edmonds_karp(graph: &Graph, source: NodeId, sink: NodeId) -> Result<MaxFlow, GraphError> {
+    graph.node(source)?;
+    graph.node(sink)?;
+    if source == sink {
+        return Err(GraphError::SourceIsSink(source));
+    }
+
+    // Room left between each pair of nodes. Sending flow one way makes
+    // room to send it back, which is how a path can undo earlier choices.
+    let mut residual: Vec<BTreeMap<NodeId, f64>> = vec![BTreeMap::new(); graph.node_bound()];
+    for node in graph.nodes() {
+        let id = node.id();
+        for (other_id, _) in graph.edges(id) {
+            if let Some(capacity) = graph.capacity(id, other_id) {
+                *residual[id.index()].entry(other_id).or_insert(0.0) += capacity;
+                residual[other_id.index()].entry(id).or_insert(0.0);
+            }
+        }
+    }
+
+    let mut value = 0.0;
+    while let Some(prev) = augmenting_path(&residual, source, sink) {
+        let mut path = vec![sink];
+        let mut id = sink;
+        while let Some(before) = prev[id.index()] {
+            path.push(before);
+            id = before;
+        }
+        path.reverse();
+
+        let room = path
+            .windows(2)
+            .map(|leg| residual[leg[0].index()][&leg[1]])
+            .fold(f64::INFINITY, f64::min);
+        for leg in path.windows(2) {
+            *residual[leg[0].index()].get_mut(&leg[1]).unwrap() -= room;
+            *residual[leg[1].index()].get_mut(&leg[0]).unwrap() += room;
+        }
+        value += room;
+    }
+
+    // Whatever an edge has lost from its capacity is flowing along it. If
+    // both directions have capacity, the flow back cancels out first.
+    let mut flows = BTreeMap::new();
+    for node in graph.nodes() {
+        let id = node.id();
+        for (other_id, _) in graph.edges(id) {
+            if let Some(capacity) = graph.capacity(id, other_id) {
+                let flow = capacity - residual[id.index()][&other_id];
+                if flow > 0.0 {
+                    flows.insert((id, other_id), flow);
+                }
+            }
+        }
+    }
+
+    let source_side = reachable(&residual, source);
+    let sink_side: BTreeSet<NodeId> = graph
+        .nodes()
+        .map(|node| node.id())
+        .filter(|id| !source_side.contains(id))
+        .collect();
+    let cut_edges = source_side
+        .iter()
+        .flat_map(|id| graph.edges(*id).map(move |(other_id, _)| (*id, other_id)))
+        .filter(|(id, other_id)| {
+            sink_side.contains(other_id) && graph.capacity(*id, *other_id).is_some()
+        })
+        .collect();
+
+    Ok(MaxFlow {
+        source,
+        sink,
+        value,
+        flows,
+        source_side,
+        sink_side,
+        cut_edges,
+    })
+}
+
+// Breadth first search through edges with room left. Gives the node each
+// was reached from, if the sink was reached at all.
+fn augmenting_path(
+    residual: &[BTreeMap<NodeId, f64>],
+    source: NodeId,
+    sink: NodeId,
+) -> Option<Vec<Option<NodeId>>> {
+    let mut prev = vec![None; residual.len()];
+    let mut discovered = vec![false; residual.len()];
+    discovered[source.index()] = true;
+    let mut queue = VecDeque::new();
+    queue.push_back(source);
+
+    while let Some(id) = queue.pop_front() {
+        for (other_id, room) in residual[id.index()].iter() {
+            if *room <= 0.0 || discovered[other_id.index()] {
+                continue;
+            }
+            discovered[other_id.index()] = true;
+            prev[other_id.index()] = Some(id);
+            if *other_id == sink {
+                return Some(prev);
+            }
+            queue.push_back(*other_id);
+        }
+    }
+
+    None
+}
+
+fn reachable(residual: &[BTreeMap<NodeId, f64>], source: NodeId) -> BTreeSet<NodeId> {
+    let mut reached = BTreeSet::new();
+    reached.insert(source);
+    let mut queue = VecDeque::new();
+    queue.push_back(source);
+
+    while let Some(id) = queue.pop_front() {
+        for (other_id, room) in residual[id.index()].iter() {
+            if *room > 0.0 && reached.insert(*other_id) {
+                queue.push_back(*other_id);
+            }
+        }
+    }
+
+    reached
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::point::Point;
+    use crate::graph::{Graph, GraphError};
+
+    // The flow network from Cormen et al., with a maximum flow of 23.
+    fn network() -> (Graph, Vec<crate::graph::node::NodeId>) {
+        let mut graph = Graph::new();
+        let ids: Vec<_> = ["s", "v1", "v2", "v3", "v4", "t"]
+            .iter()
+            .map(|name| graph.add_node(name, Point::new(0.0, 0.0)).unwrap())
+            .collect();
+        let edges = [
+            (0, 1, 16.0),
+            (0, 2, 13.0),
+            (1, 3, 12.0),
+            (2, 1, 4.0),
+            (2, 4, 14.0),
+            (3, 2, 9.0),
+            (3, 5, 20.0),
+            (4, 3, 7.0),
+            (4, 5, 4.0),
+        ];
+        for (from, to, capacity) in edges.iter() {
+            let (from, to) = (ids[*from], ids[*to]);
+            graph.add_directed_weighted_edge(from, to, 1.0).unwrap();
+            graph.set_directed_capacity(from, to, *capacity).unwrap();
+        }
+
+        (graph, ids)
+    }
+
+    #[test]
+    fn value_equals_min_cut_capacity() {
+        let (graph, ids) = network();
+        let flow = graph.max_flow(ids[0], ids[5]).unwrap();
+
+        assert_eq!(flow.value(), 23.0);
+        let cut: f64 = flow
+            .cut_edges()
+            .iter()
+            .map(|(from, to)| graph.capacity(*from, *to).unwrap())
+            .sum();
+        assert_eq!(cut, flow.value());
+        assert!(flow.source_side().contains(&ids[0]));
+        assert!(flow.sink_side().contains(&ids[5]));
+        assert_eq!(flow.source_side().len() + flow.sink_side().len(), ids.len());
+    }
+
+    #[test]
+    fn flows_respect_capacities_and_balance() {
+        let (graph, ids) = network();
+        let flow = graph.max_flow(ids[0], ids[5]).unwrap();
+
+        for ((from, to), amount) in flow.flows().iter() {
+            assert!(*amount > 0.0);
+            assert!(*amount <= graph.capacity(*from, *to).unwrap());
+        }
+        for id in ids.iter() {
+            let out: f64 = graph.edges(*id).map(|(to, _)| flow.flow(*id, to)).sum();
+            let into: f64 = graph
+                .incoming(*id)
+                .map(|(from, _)| flow.flow(from, *id))
+                .sum();
+            let expected = if *id == ids[0] {
+                23.0
+            } else if *id == ids[5] {
+                -23.0
+            } else {
+                0.0
+            };
+            assert_eq!(out - into, expected);
+        }
+    }
+
+    #[test]
+    fn rejects_bad_ends_and_capacities() {
+        let (mut graph, ids) = network();
+
+        assert_eq!(
+            graph.max_flow(ids[0], ids[0]).err(),
+            Some(GraphError::SourceIsSink(ids[0]))
+        );
+        assert_eq!(
+            graph.set_directed_capacity(ids[5], ids[0], 1.0),
+            Err(GraphError::UnknownEdge(ids[5], ids[0]))
+        );
+        assert_eq!(
+            graph.set_directed_capacity(ids[0], ids[1], -1.0),
+            Err(GraphError::InvalidCapacity(-1.0))
+        );
+        assert_eq!(graph.max_flow(ids[5], ids[0]).unwrap().value(), 0.0);
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use crate::graph::node::NodeId;
+
+// Hands out a NodeId for each distinct name, in order, and maps back and
+// forth between the two.
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    names: Vec<String>,
+    ids: HashMap<String, NodeId>,
+}
+
+// Associate functions
+impl Interner {
+    pub fn with_capacity(capacity: usize) -> Interner {
+        Interner {
+            names: Vec::with_capacity(capacity),
+            ids: HashMap::with_capacity(capacity),
+        }
+    }
+}
+
+// Public methods
+impl Interner {
+    pub fn get(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: NodeId) -> Option<&str> {
+        self.names.get(id.index()).map(String::as_str)
+    }
+
+    // Always returns a fresh id, even if the name was interned and then
+    // forgotten before.
+    pub fn intern(&mut self, name: &str) -> NodeId {
+        let id = NodeId::new(self.names.len());
+        self.names.push(name.to_owned());
+        self.ids.insert(name.to_owned(), id);
+
+        id
+    }
+
+    // The name stays readable through its old id, but no longer maps to it.
+    pub fn forget(&mut self, id: NodeId) {
+        if let Some(name) = self.names.get(id.index()) {
+            self.ids.remove(name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hands_out_ids_in_order() {
+        let mut names = Interner::default();
+        let a = names.intern("a");
+        let b = names.intern("b");
+
+        assert_eq!((a.index(), b.index()), (0, 1));
+        assert_eq!(names.get("b"), Some(b));
+        assert_eq!(names.name(a), Some("a"));
+        assert_eq!(names.get("c"), None);
+    }
+
+    #[test]
+    fn forgotten_names_get_fresh_ids() {
+        let mut names = Interner::default();
+        let old = names.intern("a");
+        names.forget(old);
+
+        assert_eq!(names.get("a"), None);
+        assert_eq!(names.name(old), Some("a"));
+        let new = names.intern("a");
+        assert_ne!(new, old);
+        assert_eq!(names.get("a"), Some(new));
+    }
+}
@@ -0,0 +1,321 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::graph::point::Point;
+use crate::graph::{Graph, GraphError};
+
+// Nodes are written one per line as `Name: row, col`, where each coordinate
+// is either a decimal number or a `42°21'29"` style geo string.
+// Edges are written one per line as `Name: Other Name`, or as
+// `Name: Other Name = 120.5` to give the weight instead of computing it.
+// Blank lines are skipped. A combined file puts the nodes first, then a
+// line of `#` characters, then the edges (see cities.txt).
+
+pub fn from_files<P: AsRef<Path>>(nodes: P, edges: P) -> Result<Graph, LoadError> {
+    let nodes = fs::read_to_string(nodes)?;
+    let edges = fs::read_to_string(edges)?;
+
+    from_strs(&nodes, &edges)
+}
+
+pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Graph, LoadError> {
+    let data = fs::read_to_string(path)?;
+
+    from_str(&data)
+}
+
+pub fn from_strs(nodes: &str, edges: &str) -> Result<Graph, LoadError> {
+    let mut graph = Graph::with_capacity(nodes.lines().count());
+
+    read_into(&mut graph, nodes, edges)?;
+
+    Ok(graph)
+}
+
+// Adds the nodes and edges to an existing graph, e.g. one made with
+// Graph::with_metric.
+pub fn read_into(graph: &mut Graph, nodes: &str, edges: &str) -> Result<(), LoadError> {
+    read_nodes(graph, nodes.lines().enumerate())?;
+    read_edges(graph, edges.lines().enumerate())
+}
+
+pub fn from_str(data: &str) -> Result<Graph, LoadError> {
+    if !data.lines().any(is_separator) {
+        return Err(LoadError::MissingSeparator);
+    }
+
+    let mut graph = Graph::new();
+    let mut lines = data.lines().enumerate();
+
+    read_nodes(
+        &mut graph,
+        lines.by_ref().take_while(|(_, line)| !is_separator(line)),
+    )?;
+    read_edges(&mut graph, lines)?;
+
+    Ok(graph)
+}
+
+fn read_nodes<'a, I>(graph: &mut Graph, lines: I) -> Result<(), LoadError>
+where
+    I: Iterator<Item = (usize, &'a str)>,
+{
+    for (i, line) in lines {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let parse_err = |kind| LoadError::Parse {
+            file: DataFile::Nodes,
+            line: i + 1,
+            kind,
+        };
+
+        let (id, coords) = split_line(line, line.rfind(':'))
+            .ok_or_else(|| parse_err(ParseErrorKind::MissingColon))?;
+        let point = parse_point(coords)
+            .ok_or_else(|| parse_err(ParseErrorKind::BadCoordinates(coords.to_owned())))?;
+
+        graph
+            .add_node(id, point)
+            .map_err(|e| parse_err(ParseErrorKind::Graph(e)))?;
+    }
+
+    Ok(())
+}
+
+fn read_edges<'a, I>(graph: &mut Graph, lines: I) -> Result<(), LoadError>
+where
+    I: Iterator<Item = (usize, &'a str)>,
+{
+    for (i, line) in lines {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let parse_err = |kind| LoadError::Parse {
+            file: DataFile::Edges,
+            line: i + 1,
+            kind,
+        };
+
+        let graph_err = |e| parse_err(ParseErrorKind::Graph(e));
+
+        let (name, rest) = split_line(line, line.find(':'))
+            .ok_or_else(|| parse_err(ParseErrorKind::MissingColon))?;
+        let (other_name, weight) = match rest.rfind('=') {
+            Some(equals) => {
+                let weight = rest[equals + 1..].trim();
+                let weight = weight
+                    .parse()
+                    .map_err(|_| parse_err(ParseErrorKind::BadWeight(weight.to_owned())))?;
+                (rest[..equals].trim(), Some(weight))
+            }
+            None => (rest, None),
+        };
+
+        let id = graph.id(name).map_err(graph_err)?;
+        let other_id = graph.id(other_name).map_err(graph_err)?;
+        match weight {
+            Some(weight) => graph.add_weighted_edge(id, other_id, weight),
+            None => graph.add_edge(id, other_id),
+        }
+        .map_err(graph_err)?;
+    }
+
+    Ok(())
+}
+
+fn split_line(line: &str, colon: Option<usize>) -> Option<(&str, &str)> {
+    let (id, rest) = line.split_at(colon?);
+    let id = id.trim();
+    let rest = rest[1..].trim();
+
+    if id.is_empty() || rest.is_empty() {
+        None
+    } else {
+        Some((id, rest))
+    }
+}
+
+fn parse_point(coords: &str) -> Option<Point> {
+    let mut parts = coords.split(',');
+    let row = parse_coordinate(parts.next()?)?;
+    let col = parse_coordinate(parts.next()?)?;
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some(Point::new(row, col))
+}
+
+// Only finite numbers: "NaN" and "inf" parse as floats, but aren't places.
+fn parse_coordinate(coord: &str) -> Option<f64> {
+    let coord = coord.trim();
+    let coord = if coord.contains('°') {
+        crate::try_gstring_parse(coord)
+    } else {
+        coord.parse().ok()
+    };
+
+    coord.filter(|coord: &f64| coord.is_finite())
+}
+
+fn is_separator(line: &str) -> bool {
+    let line = line.trim();
+
+    !line.is_empty() && line.chars().all(|c| c == '#')
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DataFile {
+    Nodes,
+    Edges,
+}
+
+impl fmt::Display for DataFile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DataFile::Nodes => write!(f, "nodes"),
+            DataFile::Edges => write!(f, "edges"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
+    MissingColon,
+    BadCoordinates(String),
+    BadWeight(String),
+    Graph(GraphError),
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseErrorKind::MissingColon => write!(f, "expected `name: value`."),
+            ParseErrorKind::BadCoordinates(coords) => {
+                write!(f, "invalid coordinates \"{}\".", coords)
+            }
+            ParseErrorKind::BadWeight(weight) => write!(f, "invalid weight \"{}\".", weight),
+            ParseErrorKind::Graph(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Io(io::Error),
+    MissingSeparator,
+    Parse {
+        file: DataFile,
+        line: usize,
+        kind: ParseErrorKind,
+    },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Io(e) => write!(f, "Could not read graph data: {}", e),
+            LoadError::MissingSeparator => {
+                write!(f, "Graph data has no `#####` line between nodes and edges.")
+            }
+            LoadError::Parse { file, line, kind } => {
+                write!(f, "Error in {} on line {}: {}", file, line, kind)
+            }
+        }
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoadError::Io(e) => Some(e),
+            LoadError::Parse {
+                kind: ParseErrorKind::Graph(e),
+                ..
+            } => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for LoadError {
+    fn from(e: io::Error) -> LoadError {
+        LoadError::Io(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(result: Result<Graph, LoadError>) -> (DataFile, usize, ParseErrorKind) {
+        match result {
+            Err(LoadError::Parse { file, line, kind }) => (file, line, kind),
+            other => panic!("expected a parse error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn reads_nodes_and_edges() {
+        let graph = from_strs(
+            "A: 0, 0\n\nB: 42°21'29\", -71°03'49\"\n",
+            "A: B\nB: A = 2.5\n",
+        )
+        .unwrap();
+
+        let (a, b) = (graph.id("A").unwrap(), graph.id("B").unwrap());
+        assert_eq!(graph.len(), 2);
+        assert_eq!(graph.weight(b, a), Some(2.5));
+        assert!(graph.weight(a, b).is_some());
+    }
+
+    #[test]
+    fn reports_the_line_of_a_bad_node() {
+        let (file, line, kind) = parse_error(from_strs("A: 0, 0\n\nB 1, 1\n", ""));
+        assert_eq!(
+            (file, line, kind),
+            (DataFile::Nodes, 3, ParseErrorKind::MissingColon)
+        );
+
+        let (file, line, kind) = parse_error(from_strs("A: 0, NaN\n", ""));
+        assert_eq!(file, DataFile::Nodes);
+        assert_eq!(line, 1);
+        assert_eq!(kind, ParseErrorKind::BadCoordinates("0, NaN".to_owned()));
+    }
+
+    #[test]
+    fn reports_the_line_of_a_bad_edge() {
+        let nodes = "A: 0, 0\nB: 1, 1\n";
+
+        let (file, line, kind) = parse_error(from_strs(nodes, "A: B\nA: C\n"));
+        assert_eq!(file, DataFile::Edges);
+        assert_eq!(line, 2);
+        assert_eq!(
+            kind,
+            ParseErrorKind::Graph(GraphError::UnknownNode("C".to_owned()))
+        );
+
+        let (_, line, kind) = parse_error(from_strs(nodes, "A: B = far\n"));
+        assert_eq!(line, 1);
+        assert_eq!(kind, ParseErrorKind::BadWeight("far".to_owned()));
+    }
+
+    #[test]
+    fn counts_lines_through_a_combined_file() {
+        let (file, line, _) = parse_error(from_str("A: 0, 0\nB: 1, 1\n###\nA: B\nB\n"));
+
+        assert_eq!((file, line), (DataFile::Edges, 5));
+        assert!(matches!(
+            from_str("A: 0, 0\n"),
+            Err(LoadError::MissingSeparator)
+        ));
+    }
+}
@@ -0,0 +1,66 @@
+use crate::graph::point::Point;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Unit {
+    Kilometres,
+    Miles,
+}
+
+impl Unit {
+    pub fn earth_radius(self) -> f64 {
+        match self {
+            Unit::Kilometres => 6371.0088,
+            Unit::Miles => 3958.7613,
+        }
+    }
+}
+
+// How Graph::add_edge turns two Points into an edge weight. Euclidean
+// treats row and col as plain coordinates. Haversine treats them as
+// latitude and longitude in degrees and gives the great-circle distance.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Metric {
+    #[default]
+    Euclidean,
+    Haversine(Unit),
+}
+
+impl Metric {
+    pub fn dist(self, one: &Point, two: &Point) -> f64 {
+        match self {
+            Metric::Euclidean => one.dist(two),
+            Metric::Haversine(unit) => one.haversine(two, unit),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn haversine_gives_great_circle_distances() {
+        let boston = Point::new(42.3581, -71.0636);
+        let new_york = Point::new(40.7128, -74.0060);
+        let km = Metric::Haversine(Unit::Kilometres);
+
+        assert!((km.dist(&boston, &new_york) - 306.1).abs() < 1.0);
+        assert_eq!(km.dist(&boston, &boston), 0.0);
+        // Halfway round the earth.
+        let antipode = Point::new(-42.3581, 108.9364);
+        let half = std::f64::consts::PI * Unit::Kilometres.earth_radius();
+        assert!((km.dist(&boston, &antipode) - half).abs() < 1e-6);
+    }
+
+    #[test]
+    fn units_scale_by_earth_radius() {
+        let one = Point::new(0.0, 0.0);
+        let two = Point::new(0.0, 1.0);
+        let km = Metric::Haversine(Unit::Kilometres).dist(&one, &two);
+        let miles = Metric::Haversine(Unit::Miles).dist(&one, &two);
+
+        let ratio = Unit::Kilometres.earth_radius() / Unit::Miles.earth_radius();
+        assert!((km / miles - ratio).abs() < 1e-12);
+        assert_eq!(Metric::Euclidean.dist(&one, &two), 1.0);
+    }
+}
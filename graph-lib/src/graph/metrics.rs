@@ -0,0 +1,417 @@
+use ordered_float::NotNan;
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap};
+
+use crate::graph::node::NodeId;
+use crate::graph::tree::dijkstra;
+use crate::graph::{Graph, GraphError};
+
+// PageRank and eigenvector centrality give up after this many rounds.
+pub const MAX_ITERATIONS: usize = 1000;
+
+// Distances below follow edge weights and directions, and assume there are
+// no negative weights, like ShortestPath.
+
+// The number of neighbours a node has whichever way the edges go, so an
+// edge added with add_edge counts once.
+pub fn degree(graph: &Graph, id: NodeId) -> usize {
+    let neighbours: BTreeSet<NodeId> = graph
+        .edges(id)
+        .chain(graph.incoming(id))
+        .map(|(id, _)| id)
+        .collect();
+
+    neighbours.len()
+}
+
+pub fn in_degree(graph: &Graph, id: NodeId) -> usize {
+    graph.incoming(id).count()
+}
+
+pub fn out_degree(graph: &Graph, id: NodeId) -> usize {
+    graph.edges(id).count()
+}
+
+// How many nodes have each degree.
+pub fn degree_distribution(graph: &Graph) -> BTreeMap<usize, usize> {
+    distribution(graph, degree)
+}
+
+pub fn in_degree_distribution(graph: &Graph) -> BTreeMap<usize, usize> {
+    distribution(graph, in_degree)
+}
+
+pub fn out_degree_distribution(graph: &Graph) -> BTreeMap<usize, usize> {
+    distribution(graph, out_degree)
+}
+
+// The distance from each node to the furthest one from it, or infinity if
+// some node can't be reached.
+pub fn eccentricities(graph: &Graph) -> BTreeMap<NodeId, f64> {
+    graph
+        .nodes()
+        .map(|node| {
+            let distances = dijkstra(graph, node.id());
+            let eccentricity = if distances.len() < graph.len() {
+                f64::INFINITY
+            } else {
+                distances
+                    .values()
+                    .map(|(_, dist)| *dist)
+                    .fold(0.0, f64::max)
+            };
+
+            (node.id(), eccentricity)
+        })
+        .collect()
+}
+
+// The smallest eccentricity. None for an empty graph.
+pub fn radius(graph: &Graph) -> Option<f64> {
+    eccentricities(graph).values().copied().reduce(f64::min)
+}
+
+// The largest eccentricity, which is infinite unless every node can reach
+// every other. None for an empty graph.
+pub fn diameter(graph: &Graph) -> Option<f64> {
+    eccentricities(graph).values().copied().reduce(f64::max)
+}
+
+// How close each node is to the rest: the number of nodes it reaches over
+// the total distance to them. That's scaled by the share of the graph it
+// reaches, so a node near a few others in a small piece of the graph
+// doesn't look central. A node reaching nothing scores 0.
+pub fn closeness_centrality(graph: &Graph) -> BTreeMap<NodeId, f64> {
+    let others = graph.len().saturating_sub(1) as f64;
+
+    graph
+        .nodes()
+        .map(|node| {
+            let distances = dijkstra(graph, node.id());
+            let reached = (distances.len() - 1) as f64;
+            let total: f64 = distances.values().map(|(_, dist)| dist).sum();
+            let closeness = if total > 0.0 {
+                (reached / total) * (reached / others)
+            } else {
+                0.0
+            };
+
+            (node.id(), closeness)
+        })
+        .collect()
+}
+
+// Brandes' algorithm: for every pair of other nodes, the share of the
+// shortest paths between them that pass through each node, added up. Pairs
+// are taken both ways round, so on a graph built with add_edge each one
+// counts twice.
+pub fn betweenness_centrality(graph: &Graph) -> BTreeMap<NodeId, f64> {
+    let size = graph.node_bound();
+    let mut betweenness = vec![0.0; size];
+
+    for source in graph.nodes().map(|node| node.id()) {
+        // Dijkstra's algorithm, also counting the shortest paths to each
+        // node and the nodes just before it on them.
+        let mut dist: Vec<Option<f64>> = vec![None; size];
+        let mut paths = vec![0.0; size];
+        let mut before: Vec<Vec<NodeId>> = vec![Vec::new(); size];
+        let mut settled = vec![false; size];
+        let mut order = Vec::new();
+
+        dist[source.index()] = Some(0.0);
+        paths[source.index()] = 1.0;
+        let mut queue = BinaryHeap::new();
+        queue.push(Reverse((NotNan::new(0.0).unwrap(), source)));
+
+        while let Some(Reverse((dist_so_far, id))) = queue.pop() {
+            if settled[id.index()] {
+                continue;
+            }
+            settled[id.index()] = true;
+            order.push(id);
+
+            for (other_id, weight) in graph.edges(id) {
+                if settled[other_id.index()] {
+                    continue;
+                }
+                let other_dist = *dist_so_far + weight;
+                match dist[other_id.index()] {
+                    Some(old) if old < other_dist => continue,
+                    Some(old) if old == other_dist => {}
+                    _ => {
+                        dist[other_id.index()] = Some(other_dist);
+                        paths[other_id.index()] = 0.0;
+                        before[other_id.index()].clear();
+                        queue.push(Reverse((NotNan::new(other_dist).unwrap(), other_id)));
+                    }
+                }
+                paths[other_id.index()] += paths[id.index()];
+                before[other_id.index()].push(id);
+            }
+        }
+
+        // Furthest first, pass back the share of paths from the source
+        // that run on through each node.
+        let mut dependency = vec![0.0; size];
+        for id in order.into_iter().rev() {
+            for prev in before[id.index()].iter() {
+                dependency[prev.index()] +=
+                    paths[prev.index()] / paths[id.index()] * (1.0 + dependency[id.index()]);
+            }
+            if id != source {
+                betweenness[id.index()] += dependency[id.index()];
+            }
+        }
+    }
+
+    scores(graph, &betweenness)
+}
+
+// The chance of ending up at each node when following edges at random,
+// jumping to any node at all with probability 1 - damping at each step.
+// Weights are ignored, as they're distances. A node with no edges out
+// jumps anywhere, so its score isn't lost. Stops once the scores change
+// by less than tolerance in total.
+pub fn pagerank(
+    graph: &Graph,
+    damping: f64,
+    tolerance: f64,
+) -> Result<BTreeMap<NodeId, f64>, GraphError> {
+    if !(0.0..=1.0).contains(&damping) {
+        return Err(GraphError::InvalidDamping(damping));
+    }
+    check_tolerance(tolerance)?;
+
+    let n = graph.len() as f64;
+    let mut rank = vec![0.0; graph.node_bound()];
+    for node in graph.nodes() {
+        rank[node.id().index()] = 1.0 / n;
+    }
+
+    for _ in 0..MAX_ITERATIONS {
+        let dangling: f64 = graph
+            .nodes()
+            .filter(|node| out_degree(graph, node.id()) == 0)
+            .map(|node| rank[node.id().index()])
+            .sum();
+
+        let mut next = vec![0.0; graph.node_bound()];
+        for node in graph.nodes() {
+            next[node.id().index()] = (1.0 - damping + damping * dangling) / n;
+        }
+        for node in graph.nodes() {
+            let id = node.id();
+            let out = out_degree(graph, id);
+            if out == 0 {
+                continue;
+            }
+
+            let share = damping * rank[id.index()] / out as f64;
+            for (other_id, _) in graph.edges(id) {
+                next[other_id.index()] += share;
+            }
+        }
+
+        let change = total_change(&rank, &next);
+        rank = next;
+        if change < tolerance {
+            return Ok(scores(graph, &rank));
+        }
+    }
+
+    Err(GraphError::NoConvergence(MAX_ITERATIONS))
+}
+
+// A node scores highly when the nodes with edges into it do. Power
+// iteration, counting each node's own score as well, which keeps it from
+// swinging back and forth forever on graphs like a single two-way edge.
+// Scores are scaled so their squares add up to 1. A directed graph that
+// isn't strongly connected can settle very slowly, and may run out of
+// iterations with GraphError::NoConvergence.
+pub fn eigenvector_centrality(
+    graph: &Graph,
+    tolerance: f64,
+) -> Result<BTreeMap<NodeId, f64>, GraphError> {
+    check_tolerance(tolerance)?;
+    if graph.is_empty() {
+        return Ok(BTreeMap::new());
+    }
+
+    let mut score = vec![0.0; graph.node_bound()];
+    for node in graph.nodes() {
+        score[node.id().index()] = 1.0 / graph.len() as f64;
+    }
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut next = score.clone();
+        for node in graph.nodes() {
+            for (other_id, _) in graph.edges(node.id()) {
+                next[other_id.index()] += score[node.id().index()];
+            }
+        }
+
+        let norm = next.iter().map(|score| score * score).sum::<f64>().sqrt();
+        for score in next.iter_mut() {
+            *score /= norm;
+        }
+
+        let change = total_change(&score, &next);
+        score = next;
+        if change < tolerance {
+            return Ok(scores(graph, &score));
+        }
+    }
+
+    Err(GraphError::NoConvergence(MAX_ITERATIONS))
+}
+
+fn check_tolerance(tolerance: f64) -> Result<(), GraphError> {
+    if tolerance.is_nan() || tolerance <= 0.0 {
+        return Err(GraphError::InvalidTolerance(tolerance));
+    }
+
+    Ok(())
+}
+
+fn total_change(old: &[f64], new: &[f64]) -> f64 {
+    old.iter()
+        .zip(new)
+        .map(|(old, new)| (new - old).abs())
+        .sum()
+}
+
+fn scores(graph: &Graph, values: &[f64]) -> BTreeMap<NodeId, f64> {
+    graph
+        .nodes()
+        .map(|node| (node.id(), values[node.id().index()]))
+        .collect()
+}
+
+fn distribution(graph: &Graph, degree: fn(&Graph, NodeId) -> usize) -> BTreeMap<usize, usize> {
+    let mut distribution = BTreeMap::new();
+    for node in graph.nodes() {
+        *distribution.entry(degree(graph, node.id())).or_insert(0) += 1;
+    }
+
+    distribution
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::point::Point;
+
+    // A centre joined to three leaves, one each.
+    fn star() -> (Graph, Vec<NodeId>) {
+        let mut graph = Graph::new();
+        let ids: Vec<_> = ["centre", "a", "b", "c"]
+            .iter()
+            .map(|name| graph.add_node(name, Point::new(0.0, 0.0)).unwrap())
+            .collect();
+        for leaf in ids[1..].iter() {
+            graph.add_weighted_edge(ids[0], *leaf, 1.0).unwrap();
+        }
+
+        (graph, ids)
+    }
+
+    #[test]
+    fn degrees() {
+        let (mut graph, ids) = star();
+        graph
+            .add_directed_weighted_edge(ids[1], ids[2], 1.0)
+            .unwrap();
+
+        assert_eq!(degree(&graph, ids[0]), 3);
+        assert_eq!(
+            (in_degree(&graph, ids[1]), out_degree(&graph, ids[1])),
+            (1, 2)
+        );
+        let expected: BTreeMap<usize, usize> = [(2, 2), (1, 1), (3, 1)].iter().copied().collect();
+        assert_eq!(degree_distribution(&graph), expected);
+    }
+
+    #[test]
+    fn eccentricity_radius_and_diameter() {
+        let (mut graph, ids) = star();
+
+        let eccentricities = eccentricities(&graph);
+        assert_eq!(eccentricities[&ids[0]], 1.0);
+        assert_eq!(eccentricities[&ids[1]], 2.0);
+        assert_eq!((radius(&graph), diameter(&graph)), (Some(1.0), Some(2.0)));
+
+        graph.add_node("apart", Point::new(0.0, 0.0)).unwrap();
+        assert_eq!(diameter(&graph), Some(f64::INFINITY));
+        assert_eq!(radius(&Graph::new()), None);
+    }
+
+    #[test]
+    fn centre_is_most_central() {
+        let (graph, ids) = star();
+
+        let closeness = closeness_centrality(&graph);
+        assert_eq!(closeness[&ids[0]], 1.0);
+        assert_eq!(closeness[&ids[1]], 0.6);
+
+        // Each pair of leaves, both ways round, goes through the centre.
+        let betweenness = betweenness_centrality(&graph);
+        assert_eq!(betweenness[&ids[0]], 6.0);
+        assert_eq!(betweenness[&ids[1]], 0.0);
+    }
+
+    #[test]
+    fn pagerank_sums_to_one() {
+        // a -> b -> c -> a, with d pointing at a and nothing pointing out
+        // of e.
+        let mut graph = Graph::new();
+        let ids: Vec<_> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|name| graph.add_node(name, Point::new(0.0, 0.0)).unwrap())
+            .collect();
+        for (from, to) in [(0, 1), (1, 2), (2, 0), (3, 0), (2, 4)].iter() {
+            graph
+                .add_directed_weighted_edge(ids[*from], ids[*to], 1.0)
+                .unwrap();
+        }
+
+        let rank = pagerank(&graph, 0.85, 1e-12).unwrap();
+        assert!((rank.values().sum::<f64>() - 1.0).abs() < 1e-9);
+        // Nothing points at d, so it only gets the random jumps.
+        let lowest = rank.values().copied().fold(f64::INFINITY, f64::min);
+        assert_eq!(rank[&ids[3]], lowest);
+        assert!(rank[&ids[1]] > rank[&ids[4]]);
+
+        // Without damping every node is just as likely.
+        let uniform = pagerank(&graph, 0.0, 1e-12).unwrap();
+        assert!(uniform.values().all(|rank| (rank - 0.2).abs() < 1e-12));
+    }
+
+    #[test]
+    fn eigenvector_centrality_favours_the_centre() {
+        let (graph, ids) = star();
+
+        let scores = eigenvector_centrality(&graph, 1e-12).unwrap();
+        assert!((scores.values().map(|score| score * score).sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(scores[&ids[0]] > scores[&ids[1]]);
+        assert!((scores[&ids[1]] - scores[&ids[2]]).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rejects_bad_parameters() {
+        let (graph, _) = star();
+
+        assert_eq!(
+            pagerank(&graph, 1.5, 1e-6),
+            Err(GraphError::InvalidDamping(1.5))
+        );
+        assert_eq!(
+            pagerank(&graph, 0.85, 0.0),
+            Err(GraphError::InvalidTolerance(0.0))
+        );
+        assert_eq!(
+            eigenvector_centrality(&graph, -1.0),
+            Err(GraphError::InvalidTolerance(-1.0))
+        );
+    }
+}
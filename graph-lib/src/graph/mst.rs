@@ -0,0 +1,60 @@
+pub mod kruskal;
+pub mod prim;
+
+use crate::graph::node::NodeId;
+use crate::graph::search::Status;
+
+// Both ends of an edge and its weight.
+pub type TreeEdge = (NodeId, NodeId, f64);
+
+// Steps through building a minimum spanning tree. Edge directions are
+// ignored, so a one-way edge can join two nodes just like a two-way one.
+pub trait SpanningTree {
+    // The edge looked at in the last step.
+    fn current(&self) -> Option<TreeEdge>;
+    // The edges still in the running to join the tree.
+    fn frontier(&self) -> Vec<TreeEdge>;
+    fn accepted(&self) -> Vec<TreeEdge>;
+    // Found once every node is in the tree, NotFound if the graph turned
+    // out not to be connected.
+    fn next(&mut self) -> Status;
+    fn result(&self) -> Option<MinimumSpanningTree>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MinimumSpanningTree {
+    edges: Vec<TreeEdge>,
+    total: f64,
+    spanning: bool,
+}
+
+// Associate functions
+impl MinimumSpanningTree {
+    fn new(edges: Vec<TreeEdge>, spanning: bool) -> MinimumSpanningTree {
+        let total = edges.iter().map(|(_, _, weight)| weight).sum();
+
+        MinimumSpanningTree {
+            edges,
+            total,
+            spanning,
+        }
+    }
+}
+
+// Public methods
+impl MinimumSpanningTree {
+    // In the order they were added to the tree.
+    pub fn edges(&self) -> &[TreeEdge] {
+        &self.edges
+    }
+
+    pub fn total(&self) -> f64 {
+        self.total
+    }
+
+    // False if the graph isn't connected, in which case Prim's tree only
+    // covers the start node's part of it and Kruskal's is a forest.
+    pub fn is_spanning(&self) -> bool {
+        self.spanning
+    }
+}
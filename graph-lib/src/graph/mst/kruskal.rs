@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+
+use crate::graph::mst::{MinimumSpanningTree, SpanningTree, TreeEdge};
+use crate::graph::node::NodeId;
+use crate::graph::search::{State, Status};
+use crate::graph::Graph;
+
+// Tracks which nodes are already joined, so an edge that would close a
+// loop can be turned down.
+#[derive(Debug)]
+struct DisjointSet {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl DisjointSet {
+    fn new(size: usize) -> DisjointSet {
+        DisjointSet {
+            parents: (0..size).collect(),
+            sizes: vec![1; size],
+        }
+    }
+
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.parents[i] != i {
+            self.parents[i] = self.parents[self.parents[i]];
+            i = self.parents[i];
+        }
+
+        i
+    }
+
+    // False if a and b were already in the same set.
+    fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.sizes[a] < self.sizes[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        true
+    }
+}
+
+// Looks at every edge once, cheapest first, keeping those that join two
+// parts of the graph not yet connected. The edges are gathered and sorted
+// on the first step.
+#[derive(Debug)]
+pub struct Kruskal<'a> {
+    graph: &'a Graph,
+    current: Option<TreeEdge>,
+    edges: Vec<TreeEdge>,
+    position: usize,
+    sets: DisjointSet,
+    needed: usize,
+    accepted: Vec<TreeEdge>,
+    state: State,
+}
+
+// Associate functions
+impl<'a> Kruskal<'a> {
+    pub fn new(graph: &'a Graph) -> Kruskal<'a> {
+        Kruskal {
+            graph,
+            current: None,
+            edges: Vec::new(),
+            position: 0,
+            sets: DisjointSet::new(graph.node_bound()),
+            needed: graph.len().saturating_sub(1),
+            accepted: Vec::new(),
+            state: State::Push,
+        }
+    }
+}
+
+// Public methods
+impl<'a> SpanningTree for Kruskal<'a> {
+    fn current(&self) -> Option<TreeEdge> {
+        self.current
+    }
+
+    fn frontier(&self) -> Vec<TreeEdge> {
+        match self.state {
+            State::Push => sorted_edges(self.graph),
+            _ => self.edges[self.position..].to_vec(),
+        }
+    }
+
+    fn accepted(&self) -> Vec<TreeEdge> {
+        self.accepted.clone()
+    }
+
+    fn next(&mut self) -> Status {
+        if let State::Done(status) = self.state {
+            return status;
+        }
+
+        if let State::Push = self.state {
+            self.edges = sorted_edges(self.graph);
+            self.state = State::Pop;
+        }
+
+        if self.accepted.len() < self.needed && self.position < self.edges.len() {
+            let (a, b, weight) = self.edges[self.position];
+            self.position += 1;
+            self.current = Some((a, b, weight));
+
+            if self.sets.union(a.index(), b.index()) {
+                self.accepted.push((a, b, weight));
+            }
+        }
+
+        if self.accepted.len() == self.needed {
+            self.state = State::Done(Status::Found);
+        } else if self.position == self.edges.len() {
+            self.state = State::Done(Status::NotFound);
+        }
+
+        match self.state {
+            State::Done(status) => status,
+            _ => Status::Searching,
+        }
+    }
+
+    fn result(&self) -> Option<MinimumSpanningTree> {
+        match self.state {
+            State::Done(status) => Some(MinimumSpanningTree::new(
+                self.accepted.clone(),
+                matches!(status, Status::Found),
+            )),
+            _ => None,
+        }
+    }
+}
+
+// One entry per pair of nodes, keeping the cheaper way if the edge is
+// stored in both directions with different weights.
+fn sorted_edges(graph: &Graph) -> Vec<TreeEdge> {
+    let mut pairs: HashMap<(NodeId, NodeId), f64> = HashMap::new();
+    for node in graph.nodes() {
+        for (to, weight) in graph.edges(node.id()) {
+            let pair = (node.id().min(to), node.id().max(to));
+            let entry = pairs.entry(pair).or_insert(weight);
+            *entry = entry.min(weight);
+        }
+    }
+
+    let mut edges: Vec<TreeEdge> = pairs.into_iter().map(|((a, b), w)| (a, b, w)).collect();
+    edges.sort_by(|a, b| {
+        a.2.partial_cmp(&b.2)
+            .unwrap()
+            .then(a.0.cmp(&b.0))
+            .then(a.1.cmp(&b.1))
+    });
+
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::mst::SpanningTree;
+    use crate::graph::point::Point;
+    use crate::graph::Graph;
+
+    #[test]
+    fn matches_prim() {
+        let mut graph = Graph::new();
+        let ids: Vec<_> = ["a", "b", "c", "d"]
+            .iter()
+            .map(|name| graph.add_node(name, Point::new(0.0, 0.0)).unwrap())
+            .collect();
+        graph.add_weighted_edge(ids[0], ids[1], 1.0).unwrap();
+        graph.add_weighted_edge(ids[1], ids[2], 2.0).unwrap();
+        graph.add_weighted_edge(ids[0], ids[2], 2.5).unwrap();
+        graph.add_weighted_edge(ids[2], ids[3], 1.5).unwrap();
+        graph.add_weighted_edge(ids[0], ids[3], 5.0).unwrap();
+
+        let kruskal = graph.kruskal();
+        assert!(kruskal.is_spanning());
+        assert_eq!(kruskal.total(), 4.5);
+        assert_eq!(kruskal.edges().len(), 3);
+        assert_eq!(graph.prim(ids[3]).unwrap().total(), kruskal.total());
+    }
+
+    #[test]
+    fn frontier_shrinks_from_every_edge() {
+        let mut graph = Graph::new();
+        let a = graph.add_node("a", Point::new(0.0, 0.0)).unwrap();
+        let b = graph.add_node("b", Point::new(0.0, 0.0)).unwrap();
+        let c = graph.add_node("c", Point::new(0.0, 0.0)).unwrap();
+        graph.add_weighted_edge(a, b, 1.0).unwrap();
+        graph.add_weighted_edge(b, c, 2.0).unwrap();
+
+        let mut tree = graph.step_kruskal();
+        assert_eq!(tree.frontier(), vec![(a, b, 1.0), (b, c, 2.0)]);
+        tree.next();
+        assert_eq!(tree.current(), Some((a, b, 1.0)));
+        assert_eq!(tree.frontier(), vec![(b, c, 2.0)]);
+    }
+
+    #[test]
+    fn forest_when_disconnected() {
+        let mut graph = Graph::new();
+        let ids: Vec<_> = ["a", "b", "c", "d"]
+            .iter()
+            .map(|name| graph.add_node(name, Point::new(0.0, 0.0)).unwrap())
+            .collect();
+        graph.add_weighted_edge(ids[0], ids[1], 1.0).unwrap();
+        graph.add_weighted_edge(ids[2], ids[3], 2.0).unwrap();
+
+        let forest = graph.kruskal();
+        assert!(!forest.is_spanning());
+        assert_eq!(forest.total(), 3.0);
+        assert!(!graph.prim(ids[0]).unwrap().is_spanning());
+    }
+}
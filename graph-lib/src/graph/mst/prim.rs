@@ -0,0 +1,127 @@
+use ordered_float::NotNan;
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+use crate::graph::mst::{MinimumSpanningTree, SpanningTree, TreeEdge};
+use crate::graph::node::NodeId;
+use crate::graph::search::{State, Status};
+use crate::graph::{Graph, GraphError};
+
+// Grows the tree out from a start node. Each Pop takes the cheapest edge
+// leaving the tree and each Push adds the edges of the node it reached.
+#[derive(Debug)]
+pub struct Prim<'a> {
+    graph: &'a Graph,
+    current: Option<TreeEdge>,
+    added: NodeId,
+    in_tree: HashSet<NodeId>,
+    queue: BinaryHeap<Reverse<(NotNan<f64>, NodeId, NodeId)>>,
+    accepted: Vec<TreeEdge>,
+    state: State,
+}
+
+// Associate functions
+impl<'a> Prim<'a> {
+    pub fn new(graph: &'a Graph, start: NodeId) -> Result<Prim<'a>, GraphError> {
+        graph.node(start)?;
+        let mut in_tree = HashSet::new();
+        in_tree.insert(start);
+
+        Ok(Prim {
+            graph,
+            current: None,
+            added: start,
+            in_tree,
+            queue: BinaryHeap::new(),
+            accepted: Vec::new(),
+            state: State::Push,
+        })
+    }
+}
+
+// Public methods
+impl<'a> SpanningTree for Prim<'a> {
+    fn current(&self) -> Option<TreeEdge> {
+        self.current
+    }
+
+    fn frontier(&self) -> Vec<TreeEdge> {
+        self.queue
+            .iter()
+            .filter(|Reverse((_, _, to))| !self.in_tree.contains(to))
+            .map(|Reverse((weight, from, to))| (*from, *to, **weight))
+            .collect()
+    }
+
+    fn accepted(&self) -> Vec<TreeEdge> {
+        self.accepted.clone()
+    }
+
+    fn next(&mut self) -> Status {
+        match self.state {
+            State::Pop => {
+                let (weight, from, to) = if let Some(Reverse(edge)) = self.queue.pop() {
+                    edge
+                } else {
+                    return self.finish();
+                };
+
+                self.current = Some((from, to, *weight));
+                if self.in_tree.contains(&to) {
+                    return Status::Searching;
+                }
+
+                self.in_tree.insert(to);
+                self.accepted.push((from, to, *weight));
+                if self.in_tree.len() == self.graph.len() {
+                    return self.finish();
+                }
+
+                self.added = to;
+                self.state = State::Push;
+                Status::Searching
+            }
+            State::Push => {
+                let from = self.added;
+                let edges = self.graph.edges(from).chain(self.graph.incoming(from));
+
+                for (to, weight) in edges {
+                    if self.in_tree.contains(&to) {
+                        continue;
+                    }
+                    self.queue
+                        .push(Reverse((NotNan::new(weight).unwrap(), from, to)));
+                }
+
+                self.state = State::Pop;
+                Status::Searching
+            }
+            State::Done(status) => status,
+        }
+    }
+
+    fn result(&self) -> Option<MinimumSpanningTree> {
+        match self.state {
+            State::Done(status) => Some(MinimumSpanningTree::new(
+                self.accepted.clone(),
+                matches!(status, Status::Found),
+            )),
+            _ => None,
+        }
+    }
+}
+
+// Private methods
+impl<'a> Prim<'a> {
+    fn finish(&mut self) -> Status {
+        let status = if self.in_tree.len() == self.graph.len() {
+            Status::Found
+        } else {
+            Status::NotFound
+        };
+        self.state = State::Done(status);
+
+        status
+    }
+}
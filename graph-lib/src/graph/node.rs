@@ -1,44 +1,50 @@
-use std::collections::BTreeMap;
+use std::fmt;
 
 use crate::graph::point::Point;
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(u32);
+
+// Associate functions
+impl NodeId {
+    pub fn new(index: usize) -> NodeId {
+        NodeId(index as u32)
+    }
+}
+
+// Public methods
+impl NodeId {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl fmt::Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Node {
-    id: String,
+    id: NodeId,
     point: Point,
-    edges: BTreeMap<String, f64>,
 }
 
 // Associate functions
 impl Node {
-    pub fn new(id: &str, point: Point) -> Node {
-        Node {
-            id: id.to_owned(),
-            point,
-            edges: BTreeMap::new(),
-        }
+    pub fn new(id: NodeId, point: Point) -> Node {
+        Node { id, point }
     }
 }
 
 // Public methods
 impl Node {
-    pub fn id(&self) -> &str {
-        &self.id
+    pub fn id(&self) -> NodeId {
+        self.id
     }
 
     pub fn point(&self) -> &Point {
         &self.point
     }
-
-    pub fn edges(&self) -> &BTreeMap<String, f64> {
-        &self.edges
-    }
-
-    pub fn add_edge(&mut self, other: &str, dist: f64) {
-        self.edges.insert(other.to_owned(), dist);
-    }
-
-    pub fn remove_edge(&mut self, id: &str) -> Option<f64> {
-        self.edges.remove(id)
-    }
 }
@@ -1,3 +1,5 @@
+use crate::graph::metric::Unit;
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Point {
     row: f64,
@@ -24,4 +26,17 @@ impl Point {
     pub fn dist(&self, other: &Point) -> f64 {
         ((self.row - other.row).powf(2.0) + (self.col - other.col).powf(2.0)).sqrt()
     }
+
+    // Great-circle distance, treating row and col as latitude and longitude.
+    pub fn haversine(&self, other: &Point, unit: Unit) -> f64 {
+        let lat_one = self.row.to_radians();
+        let lat_two = other.row.to_radians();
+        let d_lat = (other.row - self.row).to_radians();
+        let d_lon = (other.col - self.col).to_radians();
+
+        let a = (d_lat / 2.0).sin().powi(2)
+            + lat_one.cos() * lat_two.cos() * (d_lon / 2.0).sin().powi(2);
+
+        2.0 * unit.earth_radius() * a.sqrt().min(1.0).asin()
+    }
 }
@@ -1,11 +1,22 @@
+pub mod astar;
+pub mod beam;
+pub mod bellman;
 pub mod bfs;
+pub mod bibfs;
+pub mod bidijk;
 pub mod dfs;
 pub mod dijk;
+pub mod dls;
+pub mod greedy;
+pub mod iddfs;
 
-use std::rc::Rc;
-
+use std::collections::{HashMap, HashSet};
 use std::fmt::{self, Display, Formatter};
 
+use crate::graph::node::NodeId;
+use crate::graph::storage::Neighbours;
+use crate::graph::Graph;
+
 #[derive(Debug, Copy, Clone)]
 pub enum Status {
     Found,
@@ -30,11 +41,225 @@ pub enum State {
     Done(Status),
 }
 
+// Which end a bidirectional search is growing from. The backward side
+// follows edges in reverse, so it also works on directed graphs.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Direction {
+    Forward,
+    Backward,
+}
+
+impl Direction {
+    pub(crate) fn edges(self, graph: &Graph, id: NodeId) -> Neighbours<'_> {
+        match self {
+            Direction::Forward => graph.edges(id),
+            Direction::Backward => graph.incoming(id),
+        }
+    }
+}
+
+// Limits on where a search may go, without changing the graph: nodes and
+// edges to treat as missing, and how many edges a path may use at most.
+#[derive(Debug, Clone, Default)]
+pub struct SearchOptions {
+    forbidden_nodes: HashSet<NodeId>,
+    forbidden_edges: HashSet<(NodeId, NodeId)>,
+    max_hops: Option<usize>,
+}
+
+// Associate functions
+impl SearchOptions {
+    pub fn new() -> SearchOptions {
+        SearchOptions::default()
+    }
+}
+
+// Public methods
+impl SearchOptions {
+    // The start of a search is never skipped, but a forbidden destination
+    // can't be reached.
+    pub fn with_forbidden_node(mut self, id: NodeId) -> SearchOptions {
+        self.forbidden_nodes.insert(id);
+        self
+    }
+
+    // Closes the edge both ways, like one added with add_edge.
+    pub fn with_forbidden_edge(self, id: NodeId, other_id: NodeId) -> SearchOptions {
+        self.with_forbidden_directed_edge(id, other_id)
+            .with_forbidden_directed_edge(other_id, id)
+    }
+
+    pub fn with_forbidden_directed_edge(mut self, id: NodeId, other_id: NodeId) -> SearchOptions {
+        self.forbidden_edges.insert((id, other_id));
+        self
+    }
+
+    pub fn with_max_hops(mut self, hops: usize) -> SearchOptions {
+        self.max_hops = Some(hops);
+        self
+    }
+
+    pub fn forbidden_nodes(&self) -> &HashSet<NodeId> {
+        &self.forbidden_nodes
+    }
+
+    pub fn forbidden_edges(&self) -> &HashSet<(NodeId, NodeId)> {
+        &self.forbidden_edges
+    }
+
+    pub fn max_hops(&self) -> Option<usize> {
+        self.max_hops
+    }
+
+    // Whether a search may take the edge from id to other_id as the given
+    // hop along its path, counting from 1.
+    pub(crate) fn allows(&self, id: NodeId, other_id: NodeId, hop: usize) -> bool {
+        !self.forbidden_nodes.contains(&other_id)
+            && !self.forbidden_edges.contains(&(id, other_id))
+            && self.max_hops.is_none_or(|max_hops| hop <= max_hops)
+    }
+}
+
+// A node, the node it was reached from (None for the start) and the
+// distance travelled to reach it.
+pub type Entry = (NodeId, (Option<NodeId>, f64));
+
 pub trait Search {
-    fn current(&self) -> &str;
-    fn visible(&self) -> Vec<(Rc<String>, (Rc<String>, f64))>;
-    fn visited(&self) -> Vec<(Rc<String>, (Rc<String>, f64))>;
+    fn current(&self) -> Option<NodeId>;
+    fn visible(&self) -> Vec<Entry>;
+    fn visited(&self) -> Vec<Entry>;
     fn state(&self) -> State;
     fn next(&mut self) -> Status;
-    fn result(&self) -> Option<Vec<(String, f64)>>;
+    fn result(&self) -> Option<Vec<(NodeId, f64)>>;
+
+    // How many edges deep the search may currently go, for searches that
+    // have a limit.
+    fn depth_limit(&self) -> Option<usize> {
+        None
+    }
+
+    // Expansions past the first for each node, for searches that look at
+    // the same node's edges more than once.
+    fn re_expansions(&self) -> Option<usize> {
+        None
+    }
+}
+
+// Walks back from dest through the nodes each was reached from.
+pub(crate) fn make_path(
+    visited: &HashMap<NodeId, (Option<NodeId>, f64)>,
+    dest: NodeId,
+) -> Vec<(NodeId, f64)> {
+    let mut path: Vec<(NodeId, f64)> = Vec::new();
+
+    // Each node appears at most once, so parents that loop round can't
+    // keep it going forever.
+    let mut id = Some(dest);
+    while let Some(current) = id {
+        if path.len() == visited.len() {
+            break;
+        }
+        let (prev, dist) = visited[&current];
+
+        path.push((current, dist));
+        id = prev;
+    }
+
+    path.into_iter().rev().collect()
+}
+
+// Follows the forward tree from the start to meet, then the backward tree
+// from meet to the destination. Backward distances count down to the
+// destination, so they're turned around to carry on from the forward ones.
+pub(crate) fn join_paths(
+    forward: &HashMap<NodeId, (Option<NodeId>, f64)>,
+    backward: &HashMap<NodeId, (Option<NodeId>, f64)>,
+    meet: NodeId,
+) -> Vec<(NodeId, f64)> {
+    let mut path = make_path(forward, meet);
+    let total = forward[&meet].1 + backward[&meet].1;
+
+    let mut id = backward[&meet].0;
+    while let Some(current) = id {
+        let (next, remaining) = backward[&current];
+
+        path.push((current, total - remaining));
+        id = next;
+    }
+
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::point::Point;
+
+    // a - b - c - d for 1 each, plus a - e - d for 5 each.
+    fn two_ways() -> (Graph, Vec<NodeId>) {
+        let mut graph = Graph::new();
+        let ids: Vec<_> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|name| graph.add_node(name, Point::new(0.0, 0.0)).unwrap())
+            .collect();
+        for i in 0..3 {
+            graph.add_weighted_edge(ids[i], ids[i + 1], 1.0).unwrap();
+        }
+        graph.add_weighted_edge(ids[0], ids[4], 5.0).unwrap();
+        graph.add_weighted_edge(ids[4], ids[3], 5.0).unwrap();
+
+        (graph, ids)
+    }
+
+    fn nodes(path: Option<Vec<(NodeId, f64)>>) -> Option<Vec<NodeId>> {
+        path.map(|path| path.into_iter().map(|(id, _)| id).collect())
+    }
+
+    #[test]
+    fn forbidden_nodes_and_edges_are_avoided() {
+        let (graph, ids) = two_ways();
+        let around = Some(vec![ids[0], ids[4], ids[3]]);
+
+        let options = SearchOptions::new().with_forbidden_node(ids[2]);
+        let path = graph.shortest_path_with(ids[0], ids[3], options.clone());
+        assert_eq!(nodes(path.unwrap()), around);
+        let path = graph.depth_first_search_with(ids[0], ids[3], options);
+        assert_eq!(nodes(path.unwrap()), around);
+
+        // Closed both ways, so it can't be crossed from either side.
+        let options = SearchOptions::new().with_forbidden_edge(ids[2], ids[1]);
+        let path = graph.shortest_path_with(ids[0], ids[3], options);
+        assert_eq!(nodes(path.unwrap()), around);
+
+        let options = SearchOptions::new().with_forbidden_directed_edge(ids[2], ids[1]);
+        let path = graph.shortest_path_with(ids[0], ids[3], options);
+        assert_eq!(path.unwrap().unwrap().len(), 4);
+
+        let options = SearchOptions::new().with_forbidden_node(ids[3]);
+        assert_eq!(
+            graph
+                .breadth_first_search_with(ids[0], ids[3], options)
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn hop_limits_trade_distance_for_edges() {
+        let (graph, ids) = two_ways();
+
+        let options = SearchOptions::new().with_max_hops(2);
+        let path = graph.shortest_path_with(ids[0], ids[3], options.clone());
+        assert_eq!(path.unwrap().unwrap().last(), Some(&(ids[3], 10.0)));
+        let path = graph.depth_first_search_with(ids[0], ids[3], options.clone());
+        assert_eq!(nodes(path.unwrap()), Some(vec![ids[0], ids[4], ids[3]]));
+        let path = graph.breadth_first_search_with(ids[0], ids[3], options);
+        assert_eq!(path.unwrap().unwrap().len(), 3);
+
+        let options = SearchOptions::new().with_max_hops(1);
+        assert_eq!(
+            graph.shortest_path_with(ids[0], ids[3], options).unwrap(),
+            None
+        );
+    }
 }
@@ -0,0 +1,263 @@
+use ordered_float::NotNan;
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::graph::metric::Metric;
+use crate::graph::node::NodeId;
+use crate::graph::point::Point;
+use crate::graph::search::{make_path, Entry, Search, State, Status};
+use crate::graph::{Graph, GraphError};
+
+// Estimates the remaining distance from one point to another. It should
+// never overestimate, or A* may return a path that isn't the shortest.
+pub trait Heuristic {
+    fn estimate(&self, from: &Point, to: &Point) -> f64;
+}
+
+impl<F: Fn(&Point, &Point) -> f64> Heuristic for F {
+    fn estimate(&self, from: &Point, to: &Point) -> f64 {
+        self(from, to)
+    }
+}
+
+// The straight-line distance under the graph's own metric. This only
+// holds for edges whose weights came from the same metric; graphs built
+// with add_weighted_edge in other units should use astar_with instead.
+impl Heuristic for Metric {
+    fn estimate(&self, from: &Point, to: &Point) -> f64 {
+        self.dist(from, to)
+    }
+}
+
+// A heuristic that gives NaN can't say how far is left, so the node is
+// treated as being as far away as possible rather than crashing the queue.
+pub(crate) fn priority(value: f64) -> NotNan<f64> {
+    NotNan::new(value).unwrap_or_else(|_| NotNan::new(f64::INFINITY).unwrap())
+}
+
+#[derive(Debug, Eq)]
+struct Edge {
+    id: NodeId,
+    from: Option<NodeId>,
+    dist: NotNan<f64>,
+    priority: Reverse<NotNan<f64>>,
+}
+
+impl PartialEq for Edge {
+    fn eq(&self, other: &Edge) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl PartialOrd for Edge {
+    fn partial_cmp(&self, other: &Edge) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Edge {
+    fn cmp(&self, other: &Edge) -> Ordering {
+        self.priority.cmp(&other.priority)
+    }
+}
+
+impl Edge {
+    fn new(id: NodeId, from: Option<NodeId>, dist: f64, estimate: f64) -> Edge {
+        let priority = Reverse(priority(dist + estimate));
+        let dist = NotNan::new(dist).unwrap();
+        Edge {
+            id,
+            from,
+            dist,
+            priority,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AStar<'a, H: Heuristic = Metric> {
+    graph: &'a Graph,
+    heuristic: H,
+    current: Option<NodeId>,
+    dest: NodeId,
+    queue: BinaryHeap<Edge>,
+    visited: HashMap<NodeId, (Option<NodeId>, f64)>,
+    state: State,
+}
+
+// Associate functions
+impl<'a> AStar<'a> {
+    pub fn new(graph: &'a Graph, start: NodeId, dest: NodeId) -> Result<AStar<'a>, GraphError> {
+        AStar::with_heuristic(graph, start, dest, graph.metric())
+    }
+}
+
+impl<'a, H: Heuristic> AStar<'a, H> {
+    pub fn with_heuristic(
+        graph: &'a Graph,
+        start: NodeId,
+        dest: NodeId,
+        heuristic: H,
+    ) -> Result<AStar<'a, H>, GraphError> {
+        let estimate = heuristic.estimate(graph.node(start)?.point(), graph.node(dest)?.point());
+        let mut queue = BinaryHeap::new();
+        queue.push(Edge::new(start, None, 0.0, estimate));
+
+        Ok(AStar {
+            graph,
+            heuristic,
+            current: None,
+            dest,
+            queue,
+            visited: HashMap::new(),
+            state: State::Pop,
+        })
+    }
+}
+
+// Public methods
+impl<'a, H: Heuristic> Search for AStar<'a, H> {
+    fn current(&self) -> Option<NodeId> {
+        self.current
+    }
+
+    fn visible(&self) -> Vec<Entry> {
+        self.queue
+            .iter()
+            .map(|Edge { id, from, dist, .. }| (*id, (*from, **dist)))
+            .collect()
+    }
+
+    fn visited(&self) -> Vec<Entry> {
+        self.visited.iter().map(|(id, from)| (*id, *from)).collect()
+    }
+
+    fn state(&self) -> State {
+        self.state
+    }
+
+    fn next(&mut self) -> Status {
+        match self.state {
+            State::Pop => {
+                let Edge { id, from, dist, .. } = if let Some(path) = self.queue.pop() {
+                    path
+                } else {
+                    let status = Status::NotFound;
+                    self.state = State::Done(status);
+
+                    return status;
+                };
+                if self.visited.contains_key(&id) {
+                    return Status::Searching;
+                }
+                if id == self.dest {
+                    self.visited.insert(id, (from, *dist));
+                    let status = Status::Found;
+                    self.state = State::Done(status);
+
+                    return status;
+                }
+
+                self.current = Some(id);
+                self.visited.insert(id, (from, *dist));
+                self.state = State::Push;
+                Status::Searching
+            }
+            State::Push => {
+                let current = self.current.unwrap();
+                let dest = self.graph[self.dest].point();
+                let dist_so_far = self.visited[&current].1;
+
+                for (id, dist) in self.graph.edges(current) {
+                    if self.visited.contains_key(&id) {
+                        continue;
+                    }
+
+                    let estimate = self.heuristic.estimate(self.graph[id].point(), dest);
+                    self.queue
+                        .push(Edge::new(id, Some(current), dist + dist_so_far, estimate));
+                }
+
+                self.state = State::Pop;
+                Status::Searching
+            }
+            State::Done(status) => status,
+        }
+    }
+
+    fn result(&self) -> Option<Vec<(NodeId, f64)>> {
+        match &self.state {
+            State::Done(Status::Found) => Some(make_path(&self.visited, self.dest)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::node::NodeId;
+    use crate::graph::point::Point;
+    use crate::graph::search::{Search, Status};
+    use crate::graph::Graph;
+
+    // A 4 by 4 grid with edges to the right and down, weighted by distance.
+    fn grid() -> (Graph, Vec<NodeId>) {
+        let mut graph = Graph::new();
+        let mut ids = Vec::new();
+        for row in 0..4 {
+            for col in 0..4 {
+                let name = format!("{},{}", row, col);
+                ids.push(
+                    graph
+                        .add_node(&name, Point::new(row as f64, col as f64))
+                        .unwrap(),
+                );
+            }
+        }
+        for i in 0..16 {
+            if i % 4 < 3 {
+                graph.add_edge(ids[i], ids[i + 1]).unwrap();
+            }
+            if i < 12 {
+                graph.add_edge(ids[i], ids[i + 4]).unwrap();
+            }
+        }
+
+        (graph, ids)
+    }
+
+    #[test]
+    fn matches_dijkstra() {
+        let (graph, ids) = grid();
+
+        let astar = graph.astar(ids[0], ids[15]).unwrap().unwrap();
+        let dijkstra = graph.shortest_path(ids[0], ids[15]).unwrap().unwrap();
+        assert_eq!(astar.last(), dijkstra.last());
+        assert_eq!(astar.last().unwrap().1, 6.0);
+    }
+
+    #[test]
+    fn visits_fewer_nodes_than_dijkstra() {
+        let (graph, ids) = grid();
+        let mut astar = graph.step_astar(ids[0], ids[3]).unwrap();
+        while let Status::Searching = astar.next() {}
+        let mut dijkstra = graph.step_shortest_path(ids[0], ids[3]).unwrap();
+        while let Status::Searching = dijkstra.next() {}
+
+        assert!(astar.visited().len() < dijkstra.visited().len());
+    }
+
+    #[test]
+    fn survives_nan_estimates() {
+        let (graph, ids) = grid();
+
+        let path = graph
+            .astar_with(ids[0], ids[15], |_: &Point, _: &Point| f64::NAN)
+            .unwrap()
+            .unwrap();
+        // With nothing to go on the route is arbitrary, but it gets there.
+        assert_eq!(path.first().unwrap().0, ids[0]);
+        assert_eq!(path.last().unwrap().0, ids[15]);
+    }
+}
@@ -0,0 +1,237 @@
+use ordered_float::NotNan;
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::graph::metric::Metric;
+use crate::graph::node::NodeId;
+use crate::graph::search::astar::{priority, Heuristic};
+use crate::graph::search::{make_path, Entry, Search, State, Status};
+use crate::graph::{Graph, GraphError};
+
+// Breadth first search that only keeps the width nodes of each layer that
+// look closest to the destination, going by the heuristic. The queue stays
+// within a couple of layers of that width, though the visited map still
+// keeps every node expanded so the path can be rebuilt. The rest of each
+// layer is thrown away, so it can miss a path that's there, and what it
+// finds isn't necessarily the shortest.
+#[derive(Debug)]
+pub struct BeamSearch<'a, H: Heuristic = Metric> {
+    graph: &'a Graph,
+    heuristic: H,
+    width: usize,
+    current: Option<NodeId>,
+    dest: NodeId,
+    // What's left of the layer being expanded, best first.
+    layer: VecDeque<Entry>,
+    // Nodes reached from this layer so far, with their estimates. The best
+    // of them make up the next layer.
+    next_layer: Vec<(NotNan<f64>, Entry)>,
+    // Every node in layer or next_layer, so a node already waiting isn't
+    // added twice.
+    queued: HashSet<NodeId>,
+    visited: HashMap<NodeId, (Option<NodeId>, f64)>,
+    state: State,
+}
+
+// Associate functions
+impl<'a> BeamSearch<'a> {
+    pub fn new(
+        graph: &'a Graph,
+        start: NodeId,
+        dest: NodeId,
+        width: usize,
+    ) -> Result<BeamSearch<'a>, GraphError> {
+        BeamSearch::with_heuristic(graph, start, dest, width, graph.metric())
+    }
+}
+
+impl<'a, H: Heuristic> BeamSearch<'a, H> {
+    pub fn with_heuristic(
+        graph: &'a Graph,
+        start: NodeId,
+        dest: NodeId,
+        width: usize,
+        heuristic: H,
+    ) -> Result<BeamSearch<'a, H>, GraphError> {
+        graph.node(start)?;
+        graph.node(dest)?;
+        if width == 0 {
+            return Err(GraphError::InvalidWidth(width));
+        }
+        let mut layer = VecDeque::new();
+        layer.push_back((start, (None, 0.0)));
+        let mut queued = HashSet::new();
+        queued.insert(start);
+
+        Ok(BeamSearch {
+            graph,
+            heuristic,
+            width,
+            current: None,
+            dest,
+            layer,
+            next_layer: Vec::new(),
+            queued,
+            visited: HashMap::new(),
+            state: State::Pop,
+        })
+    }
+}
+
+// Public methods
+impl<'a, H: Heuristic> BeamSearch<'a, H> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+}
+
+impl<'a, H: Heuristic> Search for BeamSearch<'a, H> {
+    fn current(&self) -> Option<NodeId> {
+        self.current
+    }
+
+    fn visible(&self) -> Vec<Entry> {
+        let next_layer = self.next_layer.iter().map(|(_, entry)| *entry);
+
+        self.layer.iter().copied().chain(next_layer).collect()
+    }
+
+    fn visited(&self) -> Vec<Entry> {
+        self.visited.iter().map(|(id, from)| (*id, *from)).collect()
+    }
+
+    fn state(&self) -> State {
+        self.state
+    }
+
+    fn next(&mut self) -> Status {
+        match self.state {
+            State::Pop => {
+                if self.layer.is_empty() {
+                    self.next_layer.sort_by_key(|(estimate, _)| *estimate);
+                    let width = self.width.min(self.next_layer.len());
+                    for (_, (id, _)) in self.next_layer.drain(width..) {
+                        self.queued.remove(&id);
+                    }
+                    self.layer = self.next_layer.drain(..).map(|(_, entry)| entry).collect();
+                }
+
+                let (id, from) = if let Some(path) = self.layer.pop_front() {
+                    path
+                } else {
+                    let status = Status::NotFound;
+                    self.state = State::Done(status);
+
+                    return status;
+                };
+
+                self.queued.remove(&id);
+                self.visited.insert(id, from);
+                if id == self.dest {
+                    let status = Status::Found;
+                    self.state = State::Done(status);
+
+                    return status;
+                }
+
+                self.current = Some(id);
+                self.state = State::Push;
+                Status::Searching
+            }
+            State::Push => {
+                let current = self.current.unwrap();
+                let dest = self.graph[self.dest].point();
+                let dist_so_far = self.visited[&current].1;
+
+                for (id, dist) in self.graph.edges(current) {
+                    if self.visited.contains_key(&id) || !self.queued.insert(id) {
+                        continue;
+                    }
+
+                    let estimate = self.heuristic.estimate(self.graph[id].point(), dest);
+                    self.next_layer.push((
+                        priority(estimate),
+                        (id, (Some(current), dist + dist_so_far)),
+                    ));
+                }
+
+                self.state = State::Pop;
+                Status::Searching
+            }
+            State::Done(status) => status,
+        }
+    }
+
+    fn result(&self) -> Option<Vec<(NodeId, f64)>> {
+        match &self.state {
+            State::Done(Status::Found) => Some(make_path(&self.visited, self.dest)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::point::Point;
+    use crate::graph::search::{Search, Status};
+    use crate::graph::{Graph, GraphError};
+
+    // A start with three ways on to the destination, the closest to it
+    // going by the straight line distance being the long way round.
+    fn fan() -> (Graph, Vec<crate::graph::node::NodeId>) {
+        let mut graph = Graph::new();
+        let ids: Vec<_> = [
+            ("start", 0.0, 0.0),
+            ("near", 0.0, 9.0),
+            ("middle", 0.0, 5.0),
+            ("far", 5.0, 0.0),
+            ("end", 0.0, 10.0),
+        ]
+        .iter()
+        .map(|(name, row, col)| graph.add_node(name, Point::new(*row, *col)).unwrap())
+        .collect();
+        for (other, weight) in [(1, 20.0), (2, 5.0), (3, 5.0)].iter() {
+            graph
+                .add_weighted_edge(ids[0], ids[*other], *weight)
+                .unwrap();
+            graph
+                .add_weighted_edge(ids[*other], ids[4], *weight)
+                .unwrap();
+        }
+
+        (graph, ids)
+    }
+
+    #[test]
+    fn follows_the_best_estimates() {
+        let (graph, ids) = fan();
+
+        let path = graph.beam_search(ids[0], ids[4], 1).unwrap().unwrap();
+        assert_eq!(path, vec![(ids[0], 0.0), (ids[1], 20.0), (ids[4], 40.0)]);
+    }
+
+    #[test]
+    fn queues_each_node_once() {
+        let (graph, ids) = fan();
+        let mut search = graph.step_beam_search(ids[0], ids[4], 3).unwrap();
+
+        while let Status::Searching = search.next() {
+            let mut visible: Vec<_> = search.visible().iter().map(|(id, _)| *id).collect();
+            let len = visible.len();
+            visible.sort();
+            visible.dedup();
+            assert_eq!(visible.len(), len);
+        }
+        assert!(search.result().is_some());
+    }
+
+    #[test]
+    fn rejects_zero_width() {
+        let (graph, ids) = fan();
+
+        assert_eq!(
+            graph.beam_search(ids[0], ids[4], 0).err(),
+            Some(GraphError::InvalidWidth(0))
+        );
+    }
+}
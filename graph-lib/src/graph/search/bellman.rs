@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+
+use crate::graph::node::NodeId;
+use crate::graph::search::{make_path, Entry, Search, State, Status};
+use crate::graph::{Graph, GraphError};
+
+// Relaxes the edges of every reached node, in id order, once per pass. Each
+// Pop picks the next node in the pass and each Push relaxes its edges. With
+// n nodes the distances settle within n - 1 passes, so if the nth pass still
+// shortens a path there is a negative cycle reachable from the start.
+#[derive(Debug)]
+pub struct BellmanFord<'a> {
+    graph: &'a Graph,
+    current: Option<NodeId>,
+    start: NodeId,
+    dest: NodeId,
+    order: Vec<NodeId>,
+    position: usize,
+    pass: usize,
+    changed: bool,
+    last_relaxed: Option<NodeId>,
+    cycle: Option<Vec<NodeId>>,
+    visited: HashMap<NodeId, (Option<NodeId>, f64)>,
+    state: State,
+}
+
+// Associate functions
+impl<'a> BellmanFord<'a> {
+    pub fn new(
+        graph: &'a Graph,
+        start: NodeId,
+        dest: NodeId,
+    ) -> Result<BellmanFord<'a>, GraphError> {
+        graph.node(start)?;
+        graph.node(dest)?;
+        let mut visited = HashMap::new();
+        visited.insert(start, (None, 0.0));
+
+        Ok(BellmanFord {
+            graph,
+            current: None,
+            start,
+            dest,
+            order: graph.nodes().map(|node| node.id()).collect(),
+            position: 0,
+            pass: 1,
+            changed: false,
+            last_relaxed: None,
+            cycle: None,
+            visited,
+            state: State::Pop,
+        })
+    }
+}
+
+// Public methods
+impl<'a> BellmanFord<'a> {
+    // The negative cycle that stopped the search, in edge order, with the
+    // edge from the last node back to the first closing it.
+    pub fn negative_cycle(&self) -> Option<&[NodeId]> {
+        self.cycle.as_deref()
+    }
+}
+
+impl<'a> Search for BellmanFord<'a> {
+    fn current(&self) -> Option<NodeId> {
+        self.current
+    }
+
+    // The reached nodes still waiting for their turn in this pass.
+    fn visible(&self) -> Vec<Entry> {
+        self.order[self.position..]
+            .iter()
+            .filter_map(|id| self.visited.get(id).map(|from| (*id, *from)))
+            .collect()
+    }
+
+    fn visited(&self) -> Vec<Entry> {
+        self.visited.iter().map(|(id, from)| (*id, *from)).collect()
+    }
+
+    fn state(&self) -> State {
+        self.state
+    }
+
+    fn next(&mut self) -> Status {
+        match self.state {
+            State::Pop => {
+                while self.position < self.order.len()
+                    && !self.visited.contains_key(&self.order[self.position])
+                {
+                    self.position += 1;
+                }
+
+                if self.position < self.order.len() {
+                    self.current = Some(self.order[self.position]);
+                    self.position += 1;
+                    self.state = State::Push;
+
+                    return Status::Searching;
+                }
+
+                self.end_pass()
+            }
+            State::Push => {
+                let current = self.current.unwrap();
+                let dist_so_far = self.visited[&current].1;
+
+                for (id, dist) in self.graph.edges(current) {
+                    let dist = dist + dist_so_far;
+                    if let Some((_, old)) = self.visited.get(&id) {
+                        if *old <= dist {
+                            continue;
+                        }
+                    }
+                    // Getting back to the start for less than nothing is a
+                    // negative cycle through it. The start keeps no parent,
+                    // so every path still ends there.
+                    if id == self.start {
+                        let cycle = self
+                            .branch(current)
+                            .unwrap_or_else(|| self.find_cycle(current));
+                        return self.stop(cycle);
+                    }
+
+                    self.visited.insert(id, (Some(current), dist));
+                    self.changed = true;
+                    self.last_relaxed = Some(id);
+                }
+
+                self.state = State::Pop;
+                Status::Searching
+            }
+            State::Done(status) => status,
+        }
+    }
+
+    fn result(&self) -> Option<Vec<(NodeId, f64)>> {
+        match &self.state {
+            State::Done(Status::Found) => Some(make_path(&self.visited, self.dest)),
+            _ => None,
+        }
+    }
+}
+
+// Private methods
+impl<'a> BellmanFord<'a> {
+    fn end_pass(&mut self) -> Status {
+        if self.changed && self.pass >= self.order.len() {
+            let cycle = self.find_cycle(self.last_relaxed.unwrap());
+            return self.stop(cycle);
+        }
+
+        if self.changed {
+            self.pass += 1;
+            self.position = 0;
+            self.changed = false;
+
+            return Status::Searching;
+        }
+
+        // Rounding can shave a little off a cycle that adds up to zero and
+        // leave the parents going round it, which is as good as negative.
+        if self.visited.contains_key(&self.dest) && self.branch(self.dest).is_none() {
+            let cycle = self.find_cycle(self.dest);
+            return self.stop(cycle);
+        }
+
+        let status = if self.visited.contains_key(&self.dest) {
+            Status::Found
+        } else {
+            Status::NotFound
+        };
+        self.state = State::Done(status);
+
+        status
+    }
+
+    fn stop(&mut self, cycle: Vec<NodeId>) -> Status {
+        self.cycle = Some(cycle);
+        let status = Status::NotFound;
+        self.state = State::Done(status);
+
+        status
+    }
+
+    // The nodes from the start to id, following parents. None if they
+    // loop round without ever reaching the start.
+    fn branch(&self, id: NodeId) -> Option<Vec<NodeId>> {
+        let mut branch = vec![id];
+        let mut id = id;
+        while let Some(prev) = self.visited[&id].0 {
+            if branch.len() > self.visited.len() {
+                return None;
+            }
+            branch.push(prev);
+            id = prev;
+        }
+
+        branch.reverse();
+        Some(branch)
+    }
+
+    // Stepping back n times from a node relaxed in the nth pass, or from
+    // one whose parents loop, is guaranteed to land on the cycle, which is
+    // then followed around once.
+    fn find_cycle(&self, id: NodeId) -> Vec<NodeId> {
+        let from = |id: NodeId| {
+            self.visited[&id]
+                .0
+                .expect("a node relaxed in the last pass should lead back to a cycle")
+        };
+
+        let mut id = id;
+        for _ in 0..self.order.len() {
+            id = from(id);
+        }
+
+        let mut cycle = vec![id];
+        let mut prev = from(id);
+        while prev != id {
+            cycle.push(prev);
+            prev = from(prev);
+        }
+
+        cycle.reverse();
+        cycle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::point::Point;
+    use crate::graph::{Graph, GraphError};
+
+    fn cycle(weights: &[f64]) -> (Graph, Vec<crate::graph::node::NodeId>) {
+        let mut graph = Graph::new();
+        let ids: Vec<_> = (0..weights.len())
+            .map(|i| {
+                graph
+                    .add_node(&i.to_string(), Point::new(0.0, 0.0))
+                    .unwrap()
+            })
+            .collect();
+        for (i, weight) in weights.iter().enumerate() {
+            let next = ids[(i + 1) % ids.len()];
+            graph
+                .add_directed_weighted_edge(ids[i], next, *weight)
+                .unwrap();
+        }
+
+        (graph, ids)
+    }
+
+    #[test]
+    fn follows_negative_edges() {
+        let (mut graph, ids) = cycle(&[1.0, -0.5, 2.0]);
+        graph
+            .add_directed_weighted_edge(ids[0], ids[2], 1.0)
+            .unwrap();
+
+        let path = graph.bellman_ford(ids[0], ids[2]).unwrap().unwrap();
+        assert_eq!(path, vec![(ids[0], 0.0), (ids[1], 1.0), (ids[2], 0.5)]);
+    }
+
+    #[test]
+    fn reports_negative_cycle() {
+        let (graph, ids) = cycle(&[1.0, -0.5, -1.0]);
+
+        match graph.bellman_ford(ids[0], ids[2]) {
+            Err(GraphError::NegativeCycle(mut found)) => {
+                found.sort();
+                assert_eq!(found, ids);
+            }
+            other => panic!("expected a negative cycle, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn stops_on_cycle_that_rounds_below_zero() {
+        // 0.1 + 0.1 + 0.7 - 0.9 comes out a hair under zero.
+        let (graph, ids) = cycle(&[0.1, 0.1, 0.7, -0.9]);
+
+        match graph.bellman_ford(ids[0], ids[2]) {
+            Ok(Some(path)) => {
+                assert_eq!(path.first().unwrap().0, ids[0]);
+                assert_eq!(path.last().unwrap().0, ids[2]);
+            }
+            Err(GraphError::NegativeCycle(found)) => assert_eq!(found.len(), 4),
+            other => panic!("expected a path or a cycle, got {:?}", other),
+        }
+    }
+}
@@ -1,64 +1,72 @@
 use std::collections::HashMap;
-use std::collections::HashSet;
 use std::collections::VecDeque;
-use std::rc::Rc;
 
-use crate::graph::search::{Search, State, Status};
-use crate::graph::Graph;
+use crate::graph::node::NodeId;
+use crate::graph::search::{make_path, Entry, Search, SearchOptions, State, Status};
+use crate::graph::{Graph, GraphError};
 
 #[derive(Debug)]
-pub struct BreadthFirstSearch {
-    graph: Rc<Graph>,
-    current: Rc<String>,
-    dest: Rc<String>,
-    discovered: HashSet<Rc<String>>,
-    queue: VecDeque<(Rc<String>, (Rc<String>, f64))>,
-    visited: HashMap<Rc<String>, (Rc<String>, f64)>,
+pub struct BreadthFirstSearch<'a> {
+    graph: &'a Graph,
+    current: Option<NodeId>,
+    dest: NodeId,
+    // How many edges each node discovered is from the start. Nodes are
+    // discovered in order of hops, so the first count is the fewest.
+    hops: HashMap<NodeId, usize>,
+    queue: VecDeque<Entry>,
+    visited: HashMap<NodeId, (Option<NodeId>, f64)>,
+    options: SearchOptions,
     state: State,
 }
 
 // Associate functions
-impl BreadthFirstSearch {
-    pub fn new(graph: &Graph, start: &str, dest: &str) -> BreadthFirstSearch {
-        let graph = Rc::new(graph.clone());
-        let start = Rc::new(graph.nodes()[start].id().to_owned());
-        let dest = Rc::new(graph.nodes()[dest].id().to_owned());
-        let empty = Rc::new("".to_owned());
-        let mut discovered = HashSet::new();
-        discovered.insert(Rc::clone(&start));
+impl<'a> BreadthFirstSearch<'a> {
+    pub fn new(
+        graph: &'a Graph,
+        start: NodeId,
+        dest: NodeId,
+    ) -> Result<BreadthFirstSearch<'a>, GraphError> {
+        BreadthFirstSearch::with_options(graph, start, dest, SearchOptions::default())
+    }
+
+    pub fn with_options(
+        graph: &'a Graph,
+        start: NodeId,
+        dest: NodeId,
+        options: SearchOptions,
+    ) -> Result<BreadthFirstSearch<'a>, GraphError> {
+        graph.node(start)?;
+        graph.node(dest)?;
+        let mut hops = HashMap::new();
+        hops.insert(start, 0);
         let mut queue = VecDeque::new();
-        queue.push_back((start, (Rc::clone(&empty), 0.0)));
+        queue.push_back((start, (None, 0.0)));
 
-        BreadthFirstSearch {
+        Ok(BreadthFirstSearch {
             graph,
-            current: Rc::clone(&empty),
+            current: None,
             dest,
-            discovered,
+            hops,
             queue,
             visited: HashMap::new(),
+            options,
             state: State::Pop,
-        }
+        })
     }
 }
 
 // Public methods
-impl Search for BreadthFirstSearch {
-    fn current(&self) -> &str {
-        &self.current
+impl<'a> Search for BreadthFirstSearch<'a> {
+    fn current(&self) -> Option<NodeId> {
+        self.current
     }
 
-    fn visible(&self) -> Vec<(Rc<String>, (Rc<String>, f64))> {
-        self.queue
-            .iter()
-            .map(|(s1, (s2, f))| (Rc::clone(s1), (Rc::clone(s2), *f)))
-            .collect()
+    fn visible(&self) -> Vec<Entry> {
+        self.queue.iter().copied().collect()
     }
 
-    fn visited(&self) -> Vec<(Rc<String>, (Rc<String>, f64))> {
-        self.visited
-            .iter()
-            .map(|(s1, (s2, f))| (Rc::clone(s1), (Rc::clone(s2), *f)))
-            .collect()
+    fn visited(&self) -> Vec<Entry> {
+        self.visited.iter().map(|(id, from)| (*id, *from)).collect()
     }
 
     fn state(&self) -> State {
@@ -76,7 +84,7 @@ impl Search for BreadthFirstSearch {
 
                     return status;
                 };
-                if *id == *self.dest {
+                if id == self.dest {
                     self.visited.insert(id, (from, dist));
                     let status = Status::Found;
                     self.state = State::Done(status);
@@ -84,61 +92,78 @@ impl Search for BreadthFirstSearch {
                     return status;
                 }
 
-                self.current = Rc::clone(&id);
+                self.current = Some(id);
                 self.visited.insert(id, (from, dist));
                 self.state = State::Push;
-                return Status::Searching;
+                Status::Searching
             }
             State::Push => {
-                for (id, dist) in self.graph.nodes()[&*self.current].edges().iter() {
-                    if self.discovered.contains(id) {
+                let current = self.current.unwrap();
+                let dist_so_far = self.visited[&current].1;
+                let hop = self.hops[&current] + 1;
+
+                for (id, dist) in self.graph.edges(current) {
+                    if self.hops.contains_key(&id) || !self.options.allows(current, id, hop) {
                         continue;
                     }
-                    let id = Rc::new(id.clone());
-                    self.discovered.insert(Rc::clone(&id));
-                    self.queue.push_back((
-                        Rc::clone(&id),
-                        (
-                            Rc::clone(&self.current),
-                            *dist + self.visited[self.current.as_ref()].1,
-                        ),
-                    ));
+                    self.hops.insert(id, hop);
+                    self.queue
+                        .push_back((id, (Some(current), dist + dist_so_far)));
                 }
 
                 self.state = State::Pop;
-                return Status::Searching;
-            }
-            State::Done(status) => {
-                return status;
+                Status::Searching
             }
+            State::Done(status) => status,
         }
     }
 
-    fn result(&self) -> Option<Vec<(String, f64)>> {
+    fn result(&self) -> Option<Vec<(NodeId, f64)>> {
         match &self.state {
-            State::Done(Status::Found) => Some(self.make_path()),
+            State::Done(Status::Found) => Some(make_path(&self.visited, self.dest)),
             _ => None,
         }
     }
 }
 
-// Private methods
-impl BreadthFirstSearch {
-    fn make_path(&self) -> Vec<(String, f64)> {
-        let mut path: Vec<(String, f64)> = Vec::new();
+#[cfg(test)]
+mod tests {
+    use crate::graph::point::Point;
+    use crate::graph::search::{Search, Status};
+    use crate::graph::Graph;
 
-        let mut id = Rc::clone(&self.dest);
-        loop {
-            let (prev, dist) = &self.visited[&id];
+    // a -> d directly for 10, or a -> b -> c -> d for 3.
+    fn shortcut() -> (Graph, Vec<crate::graph::node::NodeId>) {
+        let mut graph = Graph::new();
+        let ids: Vec<_> = ["a", "b", "c", "d"]
+            .iter()
+            .map(|name| graph.add_node(name, Point::new(0.0, 0.0)).unwrap())
+            .collect();
+        graph.add_weighted_edge(ids[0], ids[3], 10.0).unwrap();
+        for i in 0..3 {
+            graph.add_weighted_edge(ids[i], ids[i + 1], 1.0).unwrap();
+        }
 
-            path.push(((*id).clone(), *dist));
-            id = Rc::clone(prev);
+        (graph, ids)
+    }
 
-            if prev.is_empty() {
-                break;
-            }
-        }
+    #[test]
+    fn finds_fewest_edges() {
+        let (graph, ids) = shortcut();
+
+        let path = graph.breadth_first_search(ids[0], ids[3]).unwrap().unwrap();
+        assert_eq!(path, vec![(ids[0], 0.0), (ids[3], 10.0)]);
+    }
+
+    #[test]
+    fn searches_share_the_graph() {
+        let (graph, ids) = shortcut();
+        let mut one = graph.step_breadth_first_search(ids[0], ids[3]).unwrap();
+        let mut two = graph.step_breadth_first_search(ids[3], ids[0]).unwrap();
 
-        path.into_iter().rev().collect()
+        while let Status::Searching = one.next() {}
+        while let Status::Searching = two.next() {}
+        assert_eq!(one.result().unwrap().len(), 2);
+        assert_eq!(two.result().unwrap().len(), 2);
     }
 }
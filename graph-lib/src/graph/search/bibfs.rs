@@ -0,0 +1,229 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::graph::node::NodeId;
+use crate::graph::search::{join_paths, Direction, Entry, Search, State, Status};
+use crate::graph::{Graph, GraphError};
+
+#[derive(Debug, Default)]
+struct Side {
+    queue: VecDeque<NodeId>,
+    discovered: HashMap<NodeId, (Option<NodeId>, f64)>,
+    hops: HashMap<NodeId, usize>,
+    visited: HashMap<NodeId, (Option<NodeId>, f64)>,
+}
+
+impl Side {
+    fn new(start: NodeId) -> Side {
+        let mut side = Side::default();
+        side.queue.push_back(start);
+        side.discovered.insert(start, (None, 0.0));
+        side.hops.insert(start, 0);
+
+        side
+    }
+
+    // Hops to the node at the front of the queue.
+    fn front(&self) -> Option<usize> {
+        self.queue.front().map(|id| self.hops[id])
+    }
+}
+
+// Grows a breadth first search from each end, always expanding the side
+// with the smaller queue. Finds the path with the fewest edges.
+#[derive(Debug)]
+pub struct BidirectionalBreadthFirstSearch<'a> {
+    graph: &'a Graph,
+    current: Option<NodeId>,
+    direction: Direction,
+    forward: Side,
+    backward: Side,
+    // Fewest hops over any meeting found so far, and where it happened.
+    best: Option<(usize, NodeId)>,
+    state: State,
+}
+
+// Associate functions
+impl<'a> BidirectionalBreadthFirstSearch<'a> {
+    pub fn new(
+        graph: &'a Graph,
+        start: NodeId,
+        dest: NodeId,
+    ) -> Result<BidirectionalBreadthFirstSearch<'a>, GraphError> {
+        graph.node(start)?;
+        graph.node(dest)?;
+
+        Ok(BidirectionalBreadthFirstSearch {
+            graph,
+            current: None,
+            direction: Direction::Forward,
+            forward: Side::new(start),
+            backward: Side::new(dest),
+            best: if start == dest {
+                Some((0, start))
+            } else {
+                None
+            },
+            state: State::Pop,
+        })
+    }
+}
+
+// Public methods
+impl<'a> Search for BidirectionalBreadthFirstSearch<'a> {
+    fn current(&self) -> Option<NodeId> {
+        self.current
+    }
+
+    fn visible(&self) -> Vec<Entry> {
+        let forward = self
+            .forward
+            .queue
+            .iter()
+            .map(|id| (*id, self.forward.discovered[id]));
+        let backward = self
+            .backward
+            .queue
+            .iter()
+            .map(|id| (*id, self.backward.discovered[id]));
+
+        forward.chain(backward).collect()
+    }
+
+    fn visited(&self) -> Vec<Entry> {
+        let forward = self.forward.visited.iter();
+        let backward = self.backward.visited.iter();
+
+        forward
+            .chain(backward)
+            .map(|(id, from)| (*id, *from))
+            .collect()
+    }
+
+    fn state(&self) -> State {
+        self.state
+    }
+
+    fn next(&mut self) -> Status {
+        match self.state {
+            State::Pop => {
+                let fronts = (self.forward.front(), self.backward.front());
+
+                // Every node still queued is at least this many hops from
+                // both ends combined, so no later meeting can beat the best.
+                let status = match (fronts, self.best) {
+                    ((Some(forward), Some(backward)), Some((hops, _)))
+                        if forward + backward >= hops =>
+                    {
+                        Some(Status::Found)
+                    }
+                    ((Some(_), Some(_)), _) => None,
+                    (_, Some(_)) => Some(Status::Found),
+                    (_, None) => Some(Status::NotFound),
+                };
+                if let Some(status) = status {
+                    self.state = State::Done(status);
+
+                    return status;
+                }
+
+                self.direction = if self.forward.queue.len() <= self.backward.queue.len() {
+                    Direction::Forward
+                } else {
+                    Direction::Backward
+                };
+                let side = match self.direction {
+                    Direction::Forward => &mut self.forward,
+                    Direction::Backward => &mut self.backward,
+                };
+
+                let id = side.queue.pop_front().unwrap();
+                side.visited.insert(id, side.discovered[&id]);
+                self.current = Some(id);
+                self.state = State::Push;
+                Status::Searching
+            }
+            State::Push => {
+                let current = self.current.unwrap();
+                let (side, other) = match self.direction {
+                    Direction::Forward => (&mut self.forward, &self.backward),
+                    Direction::Backward => (&mut self.backward, &self.forward),
+                };
+                let dist_so_far = side.discovered[&current].1;
+                let hops = side.hops[&current] + 1;
+
+                for (id, dist) in self.direction.edges(self.graph, current) {
+                    if side.discovered.contains_key(&id) {
+                        continue;
+                    }
+                    side.discovered
+                        .insert(id, (Some(current), dist + dist_so_far));
+                    side.hops.insert(id, hops);
+                    side.queue.push_back(id);
+
+                    if let Some(other_hops) = other.hops.get(&id) {
+                        let total = hops + other_hops;
+                        if self.best.is_none_or(|(best, _)| total < best) {
+                            self.best = Some((total, id));
+                        }
+                    }
+                }
+
+                self.state = State::Pop;
+                Status::Searching
+            }
+            State::Done(status) => status,
+        }
+    }
+
+    fn result(&self) -> Option<Vec<(NodeId, f64)>> {
+        match (&self.state, self.best) {
+            (State::Done(Status::Found), Some((_, meet))) => Some(join_paths(
+                &self.forward.discovered,
+                &self.backward.discovered,
+                meet,
+            )),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::point::Point;
+    use crate::graph::Graph;
+
+    #[test]
+    fn matches_breadth_first_search() {
+        // A ring of ten with one-way chords, so some pairs are nearer one
+        // way than the other.
+        let mut graph = Graph::new();
+        let ids: Vec<_> = (0..10)
+            .map(|i| {
+                graph
+                    .add_node(&i.to_string(), Point::new(0.0, 0.0))
+                    .unwrap()
+            })
+            .collect();
+        for i in 0..ids.len() {
+            graph
+                .add_weighted_edge(ids[i], ids[(i + 1) % 10], 1.0)
+                .unwrap();
+        }
+        graph
+            .add_directed_weighted_edge(ids[0], ids[5], 1.0)
+            .unwrap();
+        graph
+            .add_directed_weighted_edge(ids[7], ids[2], 1.0)
+            .unwrap();
+
+        for from in ids.iter() {
+            for to in ids.iter() {
+                let both = graph
+                    .bidirectional_breadth_first_search(*from, *to)
+                    .unwrap();
+                let one = graph.breadth_first_search(*from, *to).unwrap();
+                assert_eq!(both.unwrap().len(), one.unwrap().len());
+            }
+        }
+    }
+}
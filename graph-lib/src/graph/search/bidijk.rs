@@ -0,0 +1,252 @@
+use ordered_float::NotNan;
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::graph::node::NodeId;
+use crate::graph::search::{join_paths, Direction, Entry, Search, State, Status};
+use crate::graph::{Graph, GraphError};
+
+#[derive(Debug, Default)]
+struct Side {
+    queue: BinaryHeap<Reverse<(NotNan<f64>, NodeId)>>,
+    // The best distance found so far for each node reached.
+    labels: HashMap<NodeId, (Option<NodeId>, f64)>,
+    visited: HashMap<NodeId, (Option<NodeId>, f64)>,
+}
+
+impl Side {
+    fn new(start: NodeId) -> Side {
+        let mut side = Side::default();
+        side.queue.push(Reverse((NotNan::new(0.0).unwrap(), start)));
+        side.labels.insert(start, (None, 0.0));
+
+        side
+    }
+
+    fn front(&self) -> Option<f64> {
+        self.queue.peek().map(|Reverse((dist, _))| **dist)
+    }
+}
+
+// Grows Dijkstra's algorithm from each end, always expanding the side with
+// the smaller queue. Like ShortestPath, it assumes no negative weights.
+#[derive(Debug)]
+pub struct BidirectionalShortestPath<'a> {
+    graph: &'a Graph,
+    current: Option<NodeId>,
+    direction: Direction,
+    forward: Side,
+    backward: Side,
+    // Shortest distance over any meeting found so far, and where it happened.
+    best: Option<(f64, NodeId)>,
+    state: State,
+}
+
+// Associate functions
+impl<'a> BidirectionalShortestPath<'a> {
+    pub fn new(
+        graph: &'a Graph,
+        start: NodeId,
+        dest: NodeId,
+    ) -> Result<BidirectionalShortestPath<'a>, GraphError> {
+        graph.node(start)?;
+        graph.node(dest)?;
+
+        Ok(BidirectionalShortestPath {
+            graph,
+            current: None,
+            direction: Direction::Forward,
+            forward: Side::new(start),
+            backward: Side::new(dest),
+            best: if start == dest {
+                Some((0.0, start))
+            } else {
+                None
+            },
+            state: State::Pop,
+        })
+    }
+}
+
+// Public methods
+impl<'a> Search for BidirectionalShortestPath<'a> {
+    fn current(&self) -> Option<NodeId> {
+        self.current
+    }
+
+    fn visible(&self) -> Vec<Entry> {
+        let entry = |labels: &HashMap<NodeId, (Option<NodeId>, f64)>, dist: f64, id: NodeId| {
+            (id, (labels[&id].0, dist))
+        };
+        let forward = self
+            .forward
+            .queue
+            .iter()
+            .map(|Reverse((dist, id))| entry(&self.forward.labels, **dist, *id));
+        let backward = self
+            .backward
+            .queue
+            .iter()
+            .map(|Reverse((dist, id))| entry(&self.backward.labels, **dist, *id));
+
+        forward.chain(backward).collect()
+    }
+
+    fn visited(&self) -> Vec<Entry> {
+        let forward = self.forward.visited.iter();
+        let backward = self.backward.visited.iter();
+
+        forward
+            .chain(backward)
+            .map(|(id, from)| (*id, *from))
+            .collect()
+    }
+
+    fn state(&self) -> State {
+        self.state
+    }
+
+    fn next(&mut self) -> Status {
+        match self.state {
+            State::Pop => {
+                let fronts = (self.forward.front(), self.backward.front());
+
+                // Any path not yet found runs through a node still queued on
+                // each side, so it can't be shorter than the two fronts added.
+                let status = match (fronts, self.best) {
+                    ((Some(forward), Some(backward)), Some((best, _)))
+                        if forward + backward >= best =>
+                    {
+                        Some(Status::Found)
+                    }
+                    ((Some(_), Some(_)), _) => None,
+                    (_, Some(_)) => Some(Status::Found),
+                    (_, None) => Some(Status::NotFound),
+                };
+                if let Some(status) = status {
+                    self.state = State::Done(status);
+
+                    return status;
+                }
+
+                self.direction = if self.forward.queue.len() <= self.backward.queue.len() {
+                    Direction::Forward
+                } else {
+                    Direction::Backward
+                };
+                let side = match self.direction {
+                    Direction::Forward => &mut self.forward,
+                    Direction::Backward => &mut self.backward,
+                };
+
+                let Reverse((_, id)) = side.queue.pop().unwrap();
+                if side.visited.contains_key(&id) {
+                    return Status::Searching;
+                }
+
+                side.visited.insert(id, side.labels[&id]);
+                self.current = Some(id);
+                self.state = State::Push;
+                Status::Searching
+            }
+            State::Push => {
+                let current = self.current.unwrap();
+                let (side, other) = match self.direction {
+                    Direction::Forward => (&mut self.forward, &self.backward),
+                    Direction::Backward => (&mut self.backward, &self.forward),
+                };
+                let dist_so_far = side.labels[&current].1;
+
+                for (id, dist) in self.direction.edges(self.graph, current) {
+                    let dist = dist + dist_so_far;
+                    if side.visited.contains_key(&id) {
+                        continue;
+                    }
+                    if let Some((_, old)) = side.labels.get(&id) {
+                        if *old <= dist {
+                            continue;
+                        }
+                    }
+                    side.labels.insert(id, (Some(current), dist));
+                    side.queue.push(Reverse((NotNan::new(dist).unwrap(), id)));
+
+                    if let Some((_, other_dist)) = other.labels.get(&id) {
+                        let total = dist + other_dist;
+                        if self.best.is_none_or(|(best, _)| total < best) {
+                            self.best = Some((total, id));
+                        }
+                    }
+                }
+
+                self.state = State::Pop;
+                Status::Searching
+            }
+            State::Done(status) => status,
+        }
+    }
+
+    fn result(&self) -> Option<Vec<(NodeId, f64)>> {
+        match (&self.state, self.best) {
+            (State::Done(Status::Found), Some((_, meet))) => Some(join_paths(
+                &self.forward.labels,
+                &self.backward.labels,
+                meet,
+            )),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::point::Point;
+    use crate::graph::Graph;
+
+    #[test]
+    fn matches_dijkstra() {
+        // Directed edges with scattered weights, so the backward half has
+        // to follow incoming edges.
+        let mut graph = Graph::new();
+        let ids: Vec<_> = (0..12)
+            .map(|i| {
+                graph
+                    .add_node(&i.to_string(), Point::new(0.0, 0.0))
+                    .unwrap()
+            })
+            .collect();
+        let mut seed = 5;
+        for i in 0..ids.len() {
+            for step in [1, 3, 7].iter() {
+                seed = (seed * 17 + 11) % 23;
+                let to = ids[(i + step) % ids.len()];
+                graph
+                    .add_directed_weighted_edge(ids[i], to, seed as f64)
+                    .unwrap();
+            }
+        }
+
+        for from in ids.iter() {
+            for to in ids.iter() {
+                let both = graph.bidirectional_shortest_path(*from, *to).unwrap();
+                let one = graph.shortest_path(*from, *to).unwrap();
+                assert_eq!(
+                    both.as_ref().map(|path| path.last().unwrap().1),
+                    one.as_ref().map(|path| path.last().unwrap().1)
+                );
+                let path = both.unwrap();
+                assert_eq!((path[0].0, path.last().unwrap().0), (*from, *to));
+            }
+        }
+    }
+
+    #[test]
+    fn none_when_unreachable() {
+        let mut graph = Graph::new();
+        let a = graph.add_node("a", Point::new(0.0, 0.0)).unwrap();
+        let b = graph.add_node("b", Point::new(0.0, 0.0)).unwrap();
+        graph.add_directed_weighted_edge(b, a, 1.0).unwrap();
+
+        assert_eq!(graph.bidirectional_shortest_path(a, b).unwrap(), None);
+    }
+}
@@ -1,63 +1,69 @@
 use std::collections::HashMap;
-use std::collections::HashSet;
-use std::rc::Rc;
 
-use crate::graph::search::{Search, State, Status};
-use crate::graph::Graph;
+use crate::graph::node::NodeId;
+use crate::graph::search::{make_path, Entry, Search, SearchOptions, State, Status};
+use crate::graph::{Graph, GraphError};
 
 #[derive(Debug)]
-pub struct DepthFirstSearch {
-    graph: Rc<Graph>,
-    current: Rc<String>,
-    dest: Rc<String>,
-    discovered: HashSet<Rc<String>>,
-    stack: Vec<(Rc<String>, (Rc<String>, f64))>,
-    visited: HashMap<Rc<String>, (Rc<String>, f64)>,
+pub struct DepthFirstSearch<'a> {
+    graph: &'a Graph,
+    current: Option<NodeId>,
+    dest: NodeId,
+    // The fewest edges each node discovered has been reached in.
+    hops: HashMap<NodeId, usize>,
+    stack: Vec<(Entry, usize)>,
+    visited: HashMap<NodeId, (Option<NodeId>, f64)>,
+    options: SearchOptions,
     state: State,
 }
 
 // Associate functions
-impl DepthFirstSearch {
-    pub fn new(graph: &Graph, start: &str, dest: &str) -> DepthFirstSearch {
-        let graph = Rc::new(graph.clone());
-        let start = Rc::new(graph.nodes()[start].id().to_owned());
-        let dest = Rc::new(graph.nodes()[dest].id().to_owned());
-        let empty = Rc::new("".to_owned());
-        let mut discovered = HashSet::new();
-        discovered.insert(Rc::clone(&start));
-        let mut stack = Vec::new();
-        stack.push((start, (Rc::clone(&empty), 0.0)));
-
-        DepthFirstSearch {
+impl<'a> DepthFirstSearch<'a> {
+    pub fn new(
+        graph: &'a Graph,
+        start: NodeId,
+        dest: NodeId,
+    ) -> Result<DepthFirstSearch<'a>, GraphError> {
+        DepthFirstSearch::with_options(graph, start, dest, SearchOptions::default())
+    }
+
+    pub fn with_options(
+        graph: &'a Graph,
+        start: NodeId,
+        dest: NodeId,
+        options: SearchOptions,
+    ) -> Result<DepthFirstSearch<'a>, GraphError> {
+        graph.node(start)?;
+        graph.node(dest)?;
+        let mut hops = HashMap::new();
+        hops.insert(start, 0);
+        let stack = vec![((start, (None, 0.0)), 0)];
+
+        Ok(DepthFirstSearch {
             graph,
-            current: Rc::clone(&empty),
+            current: None,
             dest,
-            discovered,
+            hops,
             stack,
             visited: HashMap::new(),
+            options,
             state: State::Pop,
-        }
+        })
     }
 }
 
 // Public methods
-impl Search for DepthFirstSearch {
-    fn current(&self) -> &str {
-        &self.current
+impl<'a> Search for DepthFirstSearch<'a> {
+    fn current(&self) -> Option<NodeId> {
+        self.current
     }
 
-    fn visible(&self) -> Vec<(Rc<String>, (Rc<String>, f64))> {
-        self.stack
-            .iter()
-            .map(|(s1, (s2, f))| (Rc::clone(s1), (Rc::clone(s2), *f)))
-            .collect()
+    fn visible(&self) -> Vec<Entry> {
+        self.stack.iter().map(|(entry, _)| *entry).collect()
     }
 
-    fn visited(&self) -> Vec<(Rc<String>, (Rc<String>, f64))> {
-        self.visited
-            .iter()
-            .map(|(s1, (s2, f))| (Rc::clone(s1), (Rc::clone(s2), *f)))
-            .collect()
+    fn visited(&self) -> Vec<Entry> {
+        self.visited.iter().map(|(id, from)| (*id, *from)).collect()
     }
 
     fn state(&self) -> State {
@@ -67,7 +73,7 @@ impl Search for DepthFirstSearch {
     fn next(&mut self) -> Status {
         match self.state {
             State::Pop => {
-                let (id, (from, dist)) = if let Some(path) = self.stack.pop() {
+                let ((id, (from, dist)), hop) = if let Some(path) = self.stack.pop() {
                     path
                 } else {
                     let status = Status::NotFound;
@@ -75,7 +81,11 @@ impl Search for DepthFirstSearch {
 
                     return status;
                 };
-                if *id == *self.dest {
+                // Pushed again since with fewer hops.
+                if self.hops[&id] < hop {
+                    return Status::Searching;
+                }
+                if id == self.dest {
                     self.visited.insert(id, (from, dist));
                     let status = Status::Found;
                     self.state = State::Done(status);
@@ -83,61 +93,81 @@ impl Search for DepthFirstSearch {
                     return status;
                 }
 
-                self.current = Rc::clone(&id);
+                self.current = Some(id);
                 self.visited.insert(id, (from, dist));
                 self.state = State::Push;
-                return Status::Searching;
+                Status::Searching
             }
             State::Push => {
-                for (id, dist) in self.graph.nodes()[&*self.current].edges().iter() {
-                    if self.discovered.contains(id) {
+                let current = self.current.unwrap();
+                let dist_so_far = self.visited[&current].1;
+                let hop = self.hops[&current] + 1;
+
+                // In name order, as the map has always been searched, so
+                // routes don't depend on the order edges were added in.
+                let mut edges: Vec<(NodeId, f64)> = self.graph.edges(current).collect();
+                edges.sort_by_key(|(id, _)| self.graph.name(*id).unwrap());
+
+                for (id, dist) in edges {
+                    if !self.options.allows(current, id, hop) {
                         continue;
                     }
-                    let id = Rc::new(id.clone());
-                    self.discovered.insert(Rc::clone(&id));
-                    self.stack.push((
-                        Rc::clone(&id),
-                        (
-                            Rc::clone(&self.current),
-                            *dist + self.visited[self.current.as_ref()].1,
-                        ),
-                    ));
+                    // Under a hop limit, a node reached in fewer hops than
+                    // before is explored again, since it may now get further.
+                    match self.hops.get(&id) {
+                        Some(old) if self.options.max_hops().is_none() || *old <= hop => continue,
+                        _ => {}
+                    }
+                    self.hops.insert(id, hop);
+                    self.stack
+                        .push(((id, (Some(current), dist + dist_so_far)), hop));
                 }
 
                 self.state = State::Pop;
-                return Status::Searching;
-            }
-            State::Done(status) => {
-                return status;
+                Status::Searching
             }
+            State::Done(status) => status,
         }
     }
 
-    fn result(&self) -> Option<Vec<(String, f64)>> {
+    fn result(&self) -> Option<Vec<(NodeId, f64)>> {
         match &self.state {
-            State::Done(Status::Found) => Some(self.make_path()),
+            State::Done(Status::Found) => Some(make_path(&self.visited, self.dest)),
             _ => None,
         }
     }
 }
 
-// Private methods
-impl DepthFirstSearch {
-    fn make_path(&self) -> Vec<(String, f64)> {
-        let mut path: Vec<(String, f64)> = Vec::new();
-
-        let mut id = Rc::clone(&self.dest);
-        loop {
-            let (prev, dist) = &self.visited[&id];
-
-            path.push(((*id).clone(), *dist));
-            id = Rc::clone(prev);
+#[cfg(test)]
+mod tests {
+    use crate::graph::point::Point;
+    use crate::graph::Graph;
+
+    #[test]
+    fn expands_neighbours_in_name_order() {
+        // Two ways from start to end, with the edges added in the opposite
+        // order to their names.
+        let mut graph = Graph::new();
+        let start = graph.add_node("start", Point::new(0.0, 0.0)).unwrap();
+        let b = graph.add_node("b", Point::new(0.0, 0.0)).unwrap();
+        let a = graph.add_node("a", Point::new(0.0, 0.0)).unwrap();
+        let end = graph.add_node("end", Point::new(0.0, 0.0)).unwrap();
+        graph.add_weighted_edge(start, b, 1.0).unwrap();
+        graph.add_weighted_edge(start, a, 1.0).unwrap();
+        graph.add_weighted_edge(b, end, 1.0).unwrap();
+        graph.add_weighted_edge(a, end, 2.0).unwrap();
+
+        // Pushed a then b, so b comes off the stack first.
+        let path = graph.depth_first_search(start, end).unwrap().unwrap();
+        assert_eq!(path, vec![(start, 0.0), (b, 1.0), (end, 2.0)]);
+    }
 
-            if prev.is_empty() {
-                break;
-            }
-        }
+    #[test]
+    fn not_found_when_unreachable() {
+        let mut graph = Graph::new();
+        let start = graph.add_node("start", Point::new(0.0, 0.0)).unwrap();
+        let end = graph.add_node("end", Point::new(0.0, 0.0)).unwrap();
 
-        path.into_iter().rev().collect()
+        assert_eq!(graph.depth_first_search(start, end).unwrap(), None);
     }
 }
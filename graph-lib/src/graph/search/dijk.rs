@@ -3,16 +3,17 @@ use ordered_float::NotNan;
 use std::cmp::{Ordering, Reverse};
 use std::collections::{BinaryHeap, HashMap};
 use std::hash::{Hash, Hasher};
-use std::rc::Rc;
 
-use crate::graph::search::{Search, State, Status};
-use crate::graph::Graph;
+use crate::graph::node::NodeId;
+use crate::graph::search::{Entry, Search, SearchOptions, State, Status};
+use crate::graph::{Graph, GraphError};
 
 #[derive(Debug, Eq)]
 struct Edge {
-    id: Rc<String>,
-    from: Rc<String>,
+    id: NodeId,
+    from: Option<NodeId>,
     dist: Reverse<NotNan<f64>>,
+    hops: usize,
 }
 
 impl PartialEq for Edge {
@@ -23,7 +24,7 @@ impl PartialEq for Edge {
 
 impl PartialOrd for Edge {
     fn partial_cmp(&self, other: &Edge) -> Option<Ordering> {
-        Some(self.dist.cmp(&other.dist))
+        Some(self.cmp(other))
     }
 }
 
@@ -40,61 +41,84 @@ impl Hash for Edge {
 }
 
 impl Edge {
-    fn new(id: Rc<String>, from: Rc<String>, dist: f64) -> Edge {
+    fn new(id: NodeId, from: Option<NodeId>, dist: f64, hops: usize) -> Edge {
         let dist = Reverse(NotNan::new(dist).unwrap());
-        Edge { id, from, dist }
+        Edge {
+            id,
+            from,
+            dist,
+            hops,
+        }
     }
 }
 
 #[derive(Debug)]
-pub struct ShortestPath {
-    graph: Rc<Graph>,
-    current: Rc<String>,
-    dest: Rc<String>,
+pub struct ShortestPath<'a> {
+    graph: &'a Graph,
+    current: Option<NodeId>,
+    dest: NodeId,
     queue: BinaryHeap<Edge>,
-    visited: HashMap<Rc<String>, (Rc<String>, f64)>,
+    visited: HashMap<NodeId, (Option<NodeId>, f64)>,
+    // Every time a node has been popped, by how many hops it took. With no
+    // hop limit that's only once, but under one a node can be popped again
+    // if it's reached in fewer hops, as that may be the only way on to the
+    // destination. Each is reached from the one before it with a hop less.
+    settled: HashMap<(NodeId, usize), (Option<NodeId>, f64)>,
+    fewest_hops: HashMap<NodeId, usize>,
+    options: SearchOptions,
     state: State,
 }
 
 // Associate functions
-impl ShortestPath {
-    pub fn new(graph: &Graph, start: &str, dest: &str) -> ShortestPath {
-        let graph = Rc::new(graph.clone());
-        let start = Rc::new(graph.nodes()[start].id().to_owned());
-        let dest = Rc::new(graph.nodes()[dest].id().to_owned());
-        let empty = Rc::new("".to_owned());
+impl<'a> ShortestPath<'a> {
+    pub fn new(
+        graph: &'a Graph,
+        start: NodeId,
+        dest: NodeId,
+    ) -> Result<ShortestPath<'a>, GraphError> {
+        ShortestPath::with_options(graph, start, dest, SearchOptions::default())
+    }
+
+    pub fn with_options(
+        graph: &'a Graph,
+        start: NodeId,
+        dest: NodeId,
+        options: SearchOptions,
+    ) -> Result<ShortestPath<'a>, GraphError> {
+        graph.node(start)?;
+        graph.node(dest)?;
         let mut queue = BinaryHeap::new();
-        queue.push(Edge::new(start, Rc::clone(&empty), 0.0));
+        queue.push(Edge::new(start, None, 0.0, 0));
 
-        ShortestPath {
+        Ok(ShortestPath {
             graph,
-            current: Rc::clone(&empty),
+            current: None,
             dest,
             queue,
             visited: HashMap::new(),
+            settled: HashMap::new(),
+            fewest_hops: HashMap::new(),
+            options,
             state: State::Pop,
-        }
+        })
     }
 }
 
 // Public methods
-impl Search for ShortestPath {
-    fn current(&self) -> &str {
-        &self.current
+impl<'a> Search for ShortestPath<'a> {
+    fn current(&self) -> Option<NodeId> {
+        self.current
     }
 
-    fn visible(&self) -> Vec<(Rc<String>, (Rc<String>, f64))> {
+    fn visible(&self) -> Vec<Entry> {
         self.queue
             .iter()
-            .map(|Edge { id, from, dist }| (Rc::clone(id), (Rc::clone(from), *dist.0)))
+            .map(|edge| (edge.id, (edge.from, *edge.dist.0)))
             .collect()
     }
 
-    fn visited(&self) -> Vec<(Rc<String>, (Rc<String>, f64))> {
-        self.visited
-            .iter()
-            .map(|(s1, (s2, f))| (Rc::clone(s1), (Rc::clone(s2), *f)))
-            .collect()
+    fn visited(&self) -> Vec<Entry> {
+        self.visited.iter().map(|(id, from)| (*id, *from)).collect()
     }
 
     fn state(&self) -> State {
@@ -104,7 +128,12 @@ impl Search for ShortestPath {
     fn next(&mut self) -> Status {
         match self.state {
             State::Pop => {
-                let Edge { id, from, dist } = if let Some(path) = self.queue.pop() {
+                let Edge {
+                    id,
+                    from,
+                    dist,
+                    hops,
+                } = if let Some(path) = self.queue.pop() {
                     path
                 } else {
                     let status = Status::NotFound;
@@ -112,50 +141,49 @@ impl Search for ShortestPath {
 
                     return status;
                 };
-                if *id == *self.dest {
-                    self.visited.insert(id, (from, *dist.0));
+                // Without a hop limit, the first time a node is popped is
+                // along its shortest path.
+                if self.is_settled(id, hops) {
+                    return Status::Searching;
+                }
+
+                self.visited.entry(id).or_insert((from, *dist.0));
+                self.settled.insert((id, hops), (from, *dist.0));
+                self.fewest_hops.insert(id, hops);
+                if id == self.dest {
                     let status = Status::Found;
                     self.state = State::Done(status);
 
                     return status;
                 }
 
-                self.current = Rc::clone(&id);
-
-                if self.visited.contains_key(&id) && self.visited[&id].1 < *dist.0 {
-                    return Status::Searching;
-                }
-
-                self.visited.insert(id, (from, *dist.0));
+                self.current = Some(id);
                 self.state = State::Push;
-                return Status::Searching;
+                Status::Searching
             }
             State::Push => {
-                for (id, dist) in self.graph.nodes()[&*self.current].edges().iter() {
-                    let id = Rc::new(id.clone());
+                let current = self.current.unwrap();
+                let hops = self.fewest_hops[&current];
+                let dist_so_far = self.settled[&(current, hops)].1;
 
-                    if self.visited.contains_key(id.as_ref()) && self.visited[id.as_ref()].1 < *dist
+                for (id, dist) in self.graph.edges(current) {
+                    if self.is_settled(id, hops + 1) || !self.options.allows(current, id, hops + 1)
                     {
                         continue;
                     }
 
-                    self.queue.push(Edge::new(
-                        id,
-                        Rc::clone(&self.current),
-                        *dist + self.visited[self.current.as_ref()].1,
-                    ));
+                    self.queue
+                        .push(Edge::new(id, Some(current), dist + dist_so_far, hops + 1));
                 }
 
                 self.state = State::Pop;
-                return Status::Searching;
-            }
-            State::Done(status) => {
-                return status;
+                Status::Searching
             }
+            State::Done(status) => status,
         }
     }
 
-    fn result(&self) -> Option<Vec<(String, f64)>> {
+    fn result(&self) -> Option<Vec<(NodeId, f64)>> {
         match &self.state {
             State::Done(Status::Found) => Some(self.make_path()),
             _ => None,
@@ -164,22 +192,55 @@ impl Search for ShortestPath {
 }
 
 // Private methods
-impl ShortestPath {
-    fn make_path(&self) -> Vec<(String, f64)> {
-        let mut path: Vec<(String, f64)> = Vec::new();
+impl<'a> ShortestPath<'a> {
+    // Popped before with no more hops, and so with no more distance.
+    fn is_settled(&self, id: NodeId, hops: usize) -> bool {
+        match self.fewest_hops.get(&id) {
+            Some(fewest) => self.options.max_hops().is_none() || *fewest <= hops,
+            None => false,
+        }
+    }
 
-        let mut id = Rc::clone(&self.dest);
-        loop {
-            let (prev, dist) = &self.visited[&id];
+    // Like search::make_path, but through the settled nodes, stepping down
+    // a hop each time.
+    fn make_path(&self) -> Vec<(NodeId, f64)> {
+        let mut path: Vec<(NodeId, f64)> = Vec::new();
 
-            path.push(((*id).clone(), *dist));
-            id = Rc::clone(prev);
+        let mut next = Some((self.dest, self.fewest_hops[&self.dest]));
+        while let Some((id, hops)) = next {
+            let (prev, dist) = self.settled[&(id, hops)];
 
-            if prev.is_empty() {
-                break;
-            }
+            path.push((id, dist));
+            next = prev.map(|prev| (prev, hops - 1));
         }
 
         path.into_iter().rev().collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::point::Point;
+    use crate::graph::Graph;
+
+    #[test]
+    fn takes_the_cheapest_way() {
+        let mut graph = Graph::new();
+        let ids: Vec<_> = ["a", "b", "c", "d"]
+            .iter()
+            .map(|name| graph.add_node(name, Point::new(0.0, 0.0)).unwrap())
+            .collect();
+        graph.add_weighted_edge(ids[0], ids[3], 10.0).unwrap();
+        for i in 0..3 {
+            graph.add_weighted_edge(ids[i], ids[i + 1], 1.0).unwrap();
+        }
+
+        let path = graph.shortest_path(ids[0], ids[3]).unwrap().unwrap();
+        assert_eq!(path.iter().map(|(id, _)| *id).collect::<Vec<_>>(), ids);
+        assert_eq!(path.last().unwrap().1, 3.0);
+        assert_eq!(
+            graph.shortest_path(ids[3], ids[3]).unwrap().unwrap().len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+
+use crate::graph::node::NodeId;
+use crate::graph::search::{Entry, Search, State, Status};
+use crate::graph::{Graph, GraphError};
+
+// Depth first search that goes no further than limit edges from the
+// start. Only the branch it's on is checked for loops, so the stack stays
+// within the limit times the most edges out of a node.
+#[derive(Debug)]
+pub struct DepthLimitedSearch<'a> {
+    graph: &'a Graph,
+    current: Option<NodeId>,
+    start: NodeId,
+    dest: NodeId,
+    limit: usize,
+    // Each node waiting to be explored, with its depth.
+    stack: Vec<(Entry, usize)>,
+    // The path from the start to the current node.
+    branch: Vec<Entry>,
+    visited: HashMap<NodeId, (Option<NodeId>, f64)>,
+    expansions: HashMap<NodeId, usize>,
+    cut_off: bool,
+    state: State,
+}
+
+// Associate functions
+impl<'a> DepthLimitedSearch<'a> {
+    pub fn new(
+        graph: &'a Graph,
+        start: NodeId,
+        dest: NodeId,
+        limit: usize,
+    ) -> Result<DepthLimitedSearch<'a>, GraphError> {
+        graph.node(start)?;
+        graph.node(dest)?;
+
+        Ok(DepthLimitedSearch {
+            graph,
+            current: None,
+            start,
+            dest,
+            limit,
+            stack: vec![((start, (None, 0.0)), 0)],
+            branch: Vec::new(),
+            visited: HashMap::new(),
+            expansions: HashMap::new(),
+            cut_off: false,
+            state: State::Pop,
+        })
+    }
+}
+
+// Public methods
+impl<'a> DepthLimitedSearch<'a> {
+    // How many times the edges out of each node have been looked at.
+    pub fn expansions(&self) -> &HashMap<NodeId, usize> {
+        &self.expansions
+    }
+
+    // Whether the limit stopped a branch that could have gone on. If not,
+    // a deeper search wouldn't find anything more.
+    pub fn cut_off(&self) -> bool {
+        self.cut_off
+    }
+
+    // The same search over again, one edge deeper.
+    pub(crate) fn deeper(&self) -> DepthLimitedSearch<'a> {
+        DepthLimitedSearch {
+            graph: self.graph,
+            current: None,
+            start: self.start,
+            dest: self.dest,
+            limit: self.limit + 1,
+            stack: vec![((self.start, (None, 0.0)), 0)],
+            branch: Vec::new(),
+            visited: HashMap::new(),
+            expansions: HashMap::new(),
+            cut_off: false,
+            state: State::Pop,
+        }
+    }
+}
+
+impl<'a> Search for DepthLimitedSearch<'a> {
+    fn current(&self) -> Option<NodeId> {
+        self.current
+    }
+
+    fn visible(&self) -> Vec<Entry> {
+        self.stack.iter().map(|(entry, _)| *entry).collect()
+    }
+
+    fn visited(&self) -> Vec<Entry> {
+        self.visited.iter().map(|(id, from)| (*id, *from)).collect()
+    }
+
+    fn state(&self) -> State {
+        self.state
+    }
+
+    fn next(&mut self) -> Status {
+        match self.state {
+            State::Pop => {
+                let (entry, depth) = if let Some(path) = self.stack.pop() {
+                    path
+                } else {
+                    let status = Status::NotFound;
+                    self.state = State::Done(status);
+
+                    return status;
+                };
+                let (id, from) = entry;
+
+                self.branch.truncate(depth);
+                self.branch.push(entry);
+                self.visited.insert(id, from);
+                if id == self.dest {
+                    let status = Status::Found;
+                    self.state = State::Done(status);
+
+                    return status;
+                }
+
+                self.current = Some(id);
+                self.state = State::Push;
+                Status::Searching
+            }
+            State::Push => {
+                let current = self.current.unwrap();
+                let depth = self.branch.len() - 1;
+                let (_, (_, dist_so_far)) = self.branch[depth];
+                *self.expansions.entry(current).or_insert(0) += 1;
+
+                for (id, dist) in self.graph.edges(current) {
+                    // Going round in a loop never helps.
+                    if self.branch.iter().any(|(on_branch, _)| *on_branch == id) {
+                        continue;
+                    }
+                    if depth == self.limit {
+                        self.cut_off = true;
+                        break;
+                    }
+                    self.stack
+                        .push(((id, (Some(current), dist + dist_so_far)), depth + 1));
+                }
+
+                self.state = State::Pop;
+                Status::Searching
+            }
+            State::Done(status) => status,
+        }
+    }
+
+    fn result(&self) -> Option<Vec<(NodeId, f64)>> {
+        match &self.state {
+            State::Done(Status::Found) => Some(
+                self.branch
+                    .iter()
+                    .map(|(id, (_, dist))| (*id, *dist))
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+
+    fn depth_limit(&self) -> Option<usize> {
+        Some(self.limit)
+    }
+
+    // A node reachable along several branches is expanded once for each,
+    // since only the current branch is checked. The counts behind this,
+    // like visited(), grow with every node reached.
+    fn re_expansions(&self) -> Option<usize> {
+        Some(self.expansions.values().map(|count| count - 1).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::point::Point;
+    use crate::graph::search::{Search, Status};
+    use crate::graph::Graph;
+
+    // s -> a -> c -> d and s -> b -> c -> d, so c can be reached two ways.
+    fn diamond() -> (Graph, Vec<crate::graph::node::NodeId>) {
+        let mut graph = Graph::new();
+        let ids: Vec<_> = ["s", "a", "b", "c", "d", "e"]
+            .iter()
+            .map(|name| graph.add_node(name, Point::new(0.0, 0.0)).unwrap())
+            .collect();
+        for (from, to) in [(0, 1), (0, 2), (1, 3), (2, 3), (3, 4)].iter() {
+            graph.add_directed_edge(ids[*from], ids[*to]).unwrap();
+        }
+
+        (graph, ids)
+    }
+
+    #[test]
+    fn stops_at_the_limit() {
+        let (graph, ids) = diamond();
+
+        assert_eq!(graph.depth_limited_search(ids[0], ids[4], 2).unwrap(), None);
+        let path = graph
+            .depth_limited_search(ids[0], ids[4], 3)
+            .unwrap()
+            .unwrap();
+        assert_eq!(path.len(), 4);
+    }
+
+    #[test]
+    fn counts_re_expansions() {
+        let (graph, ids) = diamond();
+        // e can't be reached, so every branch is explored.
+        let mut search = graph.step_depth_limited_search(ids[0], ids[5], 3).unwrap();
+        while let Status::Searching = search.next() {}
+
+        assert_eq!(search.depth_limit(), Some(3));
+        // c, and d after it, once through a and once through b.
+        assert_eq!(search.expansions()[&ids[3]], 2);
+        assert_eq!(search.expansions()[&ids[4]], 2);
+        assert_eq!(search.re_expansions(), Some(2));
+    }
+
+    #[test]
+    fn iterative_deepening_finds_fewest_edges() {
+        let (mut graph, ids) = diamond();
+        graph.add_directed_edge(ids[1], ids[4]).unwrap();
+
+        let path = graph
+            .iterative_deepening_search(ids[0], ids[4])
+            .unwrap()
+            .unwrap();
+        assert_eq!(path.len(), 3);
+        assert_eq!(
+            graph.iterative_deepening_search(ids[0], ids[5]).unwrap(),
+            None
+        );
+    }
+}
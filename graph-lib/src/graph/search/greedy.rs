@@ -0,0 +1,195 @@
+use ordered_float::NotNan;
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::graph::metric::Metric;
+use crate::graph::node::NodeId;
+use crate::graph::search::astar::{priority, Heuristic};
+use crate::graph::search::{make_path, Entry, Search, State, Status};
+use crate::graph::{Graph, GraphError};
+
+// Always expands the node that looks closest to the destination, going by
+// the heuristic alone and ignoring the distance already travelled. It
+// often gets there in few steps, but the path it finds isn't necessarily
+// the shortest.
+#[derive(Debug)]
+pub struct GreedyBestFirst<'a, H: Heuristic = Metric> {
+    graph: &'a Graph,
+    heuristic: H,
+    current: Option<NodeId>,
+    dest: NodeId,
+    queue: BinaryHeap<Reverse<(NotNan<f64>, NodeId)>>,
+    // Where each node queued so far was first reached from.
+    discovered: HashMap<NodeId, (Option<NodeId>, f64)>,
+    visited: HashMap<NodeId, (Option<NodeId>, f64)>,
+    state: State,
+}
+
+// Associate functions
+impl<'a> GreedyBestFirst<'a> {
+    pub fn new(
+        graph: &'a Graph,
+        start: NodeId,
+        dest: NodeId,
+    ) -> Result<GreedyBestFirst<'a>, GraphError> {
+        GreedyBestFirst::with_heuristic(graph, start, dest, graph.metric())
+    }
+}
+
+impl<'a, H: Heuristic> GreedyBestFirst<'a, H> {
+    pub fn with_heuristic(
+        graph: &'a Graph,
+        start: NodeId,
+        dest: NodeId,
+        heuristic: H,
+    ) -> Result<GreedyBestFirst<'a, H>, GraphError> {
+        let estimate = heuristic.estimate(graph.node(start)?.point(), graph.node(dest)?.point());
+        let mut queue = BinaryHeap::new();
+        queue.push(Reverse((priority(estimate), start)));
+        let mut discovered = HashMap::new();
+        discovered.insert(start, (None, 0.0));
+
+        Ok(GreedyBestFirst {
+            graph,
+            heuristic,
+            current: None,
+            dest,
+            queue,
+            discovered,
+            visited: HashMap::new(),
+            state: State::Pop,
+        })
+    }
+}
+
+// Public methods
+impl<'a, H: Heuristic> Search for GreedyBestFirst<'a, H> {
+    fn current(&self) -> Option<NodeId> {
+        self.current
+    }
+
+    fn visible(&self) -> Vec<Entry> {
+        self.queue
+            .iter()
+            .map(|Reverse((_, id))| (*id, self.discovered[id]))
+            .collect()
+    }
+
+    fn visited(&self) -> Vec<Entry> {
+        self.visited.iter().map(|(id, from)| (*id, *from)).collect()
+    }
+
+    fn state(&self) -> State {
+        self.state
+    }
+
+    fn next(&mut self) -> Status {
+        match self.state {
+            State::Pop => {
+                let id = if let Some(Reverse((_, id))) = self.queue.pop() {
+                    id
+                } else {
+                    let status = Status::NotFound;
+                    self.state = State::Done(status);
+
+                    return status;
+                };
+
+                self.visited.insert(id, self.discovered[&id]);
+                if id == self.dest {
+                    let status = Status::Found;
+                    self.state = State::Done(status);
+
+                    return status;
+                }
+
+                self.current = Some(id);
+                self.state = State::Push;
+                Status::Searching
+            }
+            State::Push => {
+                let current = self.current.unwrap();
+                let dest = self.graph[self.dest].point();
+                let dist_so_far = self.visited[&current].1;
+
+                for (id, dist) in self.graph.edges(current) {
+                    if self.discovered.contains_key(&id) {
+                        continue;
+                    }
+
+                    let estimate = self.heuristic.estimate(self.graph[id].point(), dest);
+                    self.discovered
+                        .insert(id, (Some(current), dist + dist_so_far));
+                    self.queue.push(Reverse((priority(estimate), id)));
+                }
+
+                self.state = State::Pop;
+                Status::Searching
+            }
+            State::Done(status) => status,
+        }
+    }
+
+    fn result(&self) -> Option<Vec<(NodeId, f64)>> {
+        match &self.state {
+            State::Done(Status::Found) => Some(make_path(&self.visited, self.dest)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::point::Point;
+
+    // Three ways from start to end. The one through near looks best going
+    // by straight line distances but is by far the longest.
+    fn fan() -> (Graph, Vec<NodeId>) {
+        let mut graph = Graph::new();
+        let ids: Vec<_> = [
+            ("start", 0.0, 0.0),
+            ("near", 0.0, 9.0),
+            ("middle", 0.0, 5.0),
+            ("far", 5.0, 0.0),
+            ("end", 0.0, 10.0),
+        ]
+        .iter()
+        .map(|(name, row, col)| graph.add_node(name, Point::new(*row, *col)).unwrap())
+        .collect();
+        for (other, weight) in [(1, 20.0), (2, 5.0), (3, 5.0)].iter() {
+            graph
+                .add_weighted_edge(ids[0], ids[*other], *weight)
+                .unwrap();
+            graph
+                .add_weighted_edge(ids[*other], ids[4], *weight)
+                .unwrap();
+        }
+
+        (graph, ids)
+    }
+
+    #[test]
+    fn follows_the_estimate_not_the_distance() {
+        let (graph, ids) = fan();
+
+        let greedy = graph
+            .greedy_best_first_search(ids[0], ids[4])
+            .unwrap()
+            .unwrap();
+        assert_eq!(greedy, vec![(ids[0], 0.0), (ids[1], 20.0), (ids[4], 40.0)]);
+        let shortest = graph.shortest_path(ids[0], ids[4]).unwrap().unwrap();
+        assert_eq!(shortest.last().unwrap().1, 10.0);
+    }
+
+    #[test]
+    fn survives_nan_estimates() {
+        let (graph, ids) = fan();
+        let nan = |_: &Point, _: &Point| f64::NAN;
+        let mut search = GreedyBestFirst::with_heuristic(&graph, ids[0], ids[4], nan).unwrap();
+
+        while let Status::Searching = search.next() {}
+        assert_eq!(search.result().unwrap().last().unwrap().0, ids[4]);
+    }
+}
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use crate::graph::node::NodeId;
+use crate::graph::search::dls::DepthLimitedSearch;
+use crate::graph::search::{Entry, Search, State, Status};
+use crate::graph::{Graph, GraphError};
+
+// Depth limited searches with a limit of 0, 1, 2 and so on, until one
+// finds the destination or isn't cut off by its limit. Finds the path with
+// the fewest edges, like BreadthFirstSearch, with a stack that only grows
+// with the depth, paid for by expanding the shallow nodes again each
+// round. Everything but expansions() and re_expansions() covers just the
+// current round.
+#[derive(Debug)]
+pub struct IterativeDeepeningSearch<'a> {
+    round: DepthLimitedSearch<'a>,
+    // Expansions from the rounds before this one.
+    earlier: HashMap<NodeId, usize>,
+}
+
+// Associate functions
+impl<'a> IterativeDeepeningSearch<'a> {
+    pub fn new(
+        graph: &'a Graph,
+        start: NodeId,
+        dest: NodeId,
+    ) -> Result<IterativeDeepeningSearch<'a>, GraphError> {
+        Ok(IterativeDeepeningSearch {
+            round: DepthLimitedSearch::new(graph, start, dest, 0)?,
+            earlier: HashMap::new(),
+        })
+    }
+}
+
+// Public methods
+impl<'a> IterativeDeepeningSearch<'a> {
+    // How many times the edges out of each node have been looked at, over
+    // every round so far.
+    pub fn expansions(&self) -> HashMap<NodeId, usize> {
+        let mut expansions = self.earlier.clone();
+        for (id, count) in self.round.expansions() {
+            *expansions.entry(*id).or_insert(0) += count;
+        }
+
+        expansions
+    }
+}
+
+impl<'a> Search for IterativeDeepeningSearch<'a> {
+    fn current(&self) -> Option<NodeId> {
+        self.round.current()
+    }
+
+    fn visible(&self) -> Vec<Entry> {
+        self.round.visible()
+    }
+
+    fn visited(&self) -> Vec<Entry> {
+        self.round.visited()
+    }
+
+    fn state(&self) -> State {
+        self.round.state()
+    }
+
+    fn next(&mut self) -> Status {
+        match self.round.next() {
+            Status::NotFound if self.round.cut_off() => {
+                for (id, count) in self.round.expansions() {
+                    *self.earlier.entry(*id).or_insert(0) += count;
+                }
+                self.round = self.round.deeper();
+
+                Status::Searching
+            }
+            status => status,
+        }
+    }
+
+    fn result(&self) -> Option<Vec<(NodeId, f64)>> {
+        self.round.result()
+    }
+
+    // The limit of the current round.
+    fn depth_limit(&self) -> Option<usize> {
+        self.round.depth_limit()
+    }
+
+    // Over every round so far.
+    fn re_expansions(&self) -> Option<usize> {
+        Some(self.expansions().values().map(|count| count - 1).sum())
+    }
+}
@@ -0,0 +1,111 @@
+pub mod csr;
+pub mod list;
+pub mod map;
+pub mod matrix;
+
+use std::fmt::Debug;
+
+use crate::graph::node::NodeId;
+use crate::graph::GraphError;
+
+pub type Neighbours<'a> = Box<dyn Iterator<Item = (NodeId, f64)> + 'a>;
+
+// Where a Graph keeps its edges. Node ids are handed out densely from 0
+// by the Graph, which also does all validation, so a Storage only needs
+// to make room for each new id and keep track of weighted, directed edges.
+pub trait Storage: Debug {
+    fn add_node(&mut self, id: NodeId) -> Result<(), GraphError>;
+    fn remove_node(&mut self, id: NodeId) -> Result<(), GraphError>;
+    fn add_edge(&mut self, from: NodeId, to: NodeId, weight: f64) -> Result<(), GraphError>;
+    fn remove_edge(&mut self, from: NodeId, to: NodeId) -> Result<Option<f64>, GraphError>;
+    fn weight(&self, from: NodeId, to: NodeId) -> Option<f64>;
+    // Empty for ids the storage doesn't know about.
+    fn neighbours(&self, id: NodeId) -> Neighbours<'_>;
+    // The edges leading into id, as (from, weight).
+    fn incoming(&self, id: NodeId) -> Neighbours<'_>;
+    fn box_clone(&self) -> Box<dyn Storage>;
+}
+
+impl Clone for Box<dyn Storage> {
+    fn clone(&self) -> Box<dyn Storage> {
+        self.box_clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::csr::CompressedSparseRow;
+    use super::list::AdjacencyList;
+    use super::map::AdjacencyMap;
+    use super::matrix::AdjacencyMatrix;
+    use super::*;
+
+    // Three nodes, a removed middle one, and edges both ways between the
+    // others.
+    fn fill(storage: &mut dyn Storage) {
+        for i in 0..3 {
+            storage.add_node(NodeId::new(i)).unwrap();
+        }
+        storage
+            .add_edge(NodeId::new(0), NodeId::new(1), 1.0)
+            .unwrap();
+        storage
+            .add_edge(NodeId::new(0), NodeId::new(2), 2.0)
+            .unwrap();
+        storage
+            .add_edge(NodeId::new(2), NodeId::new(0), 3.0)
+            .unwrap();
+        storage.remove_node(NodeId::new(1)).unwrap();
+    }
+
+    fn check(storage: &dyn Storage) {
+        let (a, b, c) = (NodeId::new(0), NodeId::new(1), NodeId::new(2));
+        let mut out: Vec<_> = storage.neighbours(a).collect();
+        out.sort_by_key(|(id, _)| *id);
+
+        assert_eq!(out, vec![(c, 2.0)]);
+        assert_eq!(storage.incoming(a).collect::<Vec<_>>(), vec![(c, 3.0)]);
+        assert_eq!(storage.weight(c, a), Some(3.0));
+        assert_eq!(storage.weight(a, b), None);
+        assert_eq!(storage.neighbours(NodeId::new(9)).count(), 0);
+    }
+
+    #[test]
+    fn backends_agree() {
+        let mut backends: Vec<Box<dyn Storage>> = vec![
+            Box::new(AdjacencyMap::default()),
+            Box::new(AdjacencyList::default()),
+            Box::new(AdjacencyMatrix::default()),
+        ];
+
+        for storage in backends.iter_mut() {
+            fill(&mut **storage);
+            check(&**storage);
+
+            assert_eq!(
+                storage.remove_edge(NodeId::new(2), NodeId::new(0)),
+                Ok(Some(3.0))
+            );
+            assert_eq!(storage.incoming(NodeId::new(0)).count(), 0);
+        }
+    }
+
+    #[test]
+    fn compressed_rows_copy_and_refuse_changes() {
+        let mut map = AdjacencyMap::default();
+        fill(&mut map);
+        let mut csr = CompressedSparseRow::from_storage(&map, 3);
+        check(&csr);
+
+        assert_eq!(csr.add_node(NodeId::new(3)), Err(GraphError::Frozen));
+        assert_eq!(
+            csr.add_edge(NodeId::new(0), NodeId::new(2), 1.0),
+            Err(GraphError::Frozen)
+        );
+        assert_eq!(
+            csr.remove_edge(NodeId::new(0), NodeId::new(2)),
+            Err(GraphError::Frozen)
+        );
+        assert_eq!(csr.remove_node(NodeId::new(0)), Err(GraphError::Frozen));
+    }
+}
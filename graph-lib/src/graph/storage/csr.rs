@@ -0,0 +1,124 @@
+use crate::graph::node::NodeId;
+use crate::graph::storage::{Neighbours, Storage};
+use crate::graph::GraphError;
+
+// Compressed sparse row layout: every edge in one pair of arrays, sorted by
+// source node, with an offset per node into them. Compact and fast to walk,
+// but it can't be changed once built. See Graph::freeze.
+#[derive(Debug, Clone, Default)]
+pub struct CompressedSparseRow {
+    outgoing: Rows,
+    // The same edges again, sorted by the node they lead to.
+    incoming: Rows,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Rows {
+    offsets: Vec<usize>,
+    targets: Vec<NodeId>,
+    weights: Vec<f64>,
+}
+
+// Associate functions
+impl CompressedSparseRow {
+    pub fn from_storage(storage: &dyn Storage, node_bound: usize) -> CompressedSparseRow {
+        CompressedSparseRow {
+            outgoing: Rows::new(node_bound, |id| storage.neighbours(id)),
+            incoming: Rows::new(node_bound, |id| storage.incoming(id)),
+        }
+    }
+}
+
+impl Rows {
+    fn new<'a, F>(node_bound: usize, edges: F) -> Rows
+    where
+        F: Fn(NodeId) -> Neighbours<'a>,
+    {
+        let mut offsets = Vec::with_capacity(node_bound + 1);
+        let mut targets = Vec::new();
+        let mut weights = Vec::new();
+
+        offsets.push(0);
+        for i in 0..node_bound {
+            let mut row: Vec<(NodeId, f64)> = edges(NodeId::new(i)).collect();
+            row.sort_by_key(|(id, _)| *id);
+
+            for (id, weight) in row {
+                targets.push(id);
+                weights.push(weight);
+            }
+            offsets.push(targets.len());
+        }
+
+        Rows {
+            offsets,
+            targets,
+            weights,
+        }
+    }
+}
+
+// Private methods
+impl Rows {
+    fn range(&self, id: NodeId) -> Option<(usize, usize)> {
+        let start = *self.offsets.get(id.index())?;
+        let end = *self.offsets.get(id.index() + 1)?;
+
+        Some((start, end))
+    }
+
+    fn weight(&self, from: NodeId, to: NodeId) -> Option<f64> {
+        let (start, end) = self.range(from)?;
+
+        self.targets[start..end]
+            .binary_search(&to)
+            .ok()
+            .map(|i| self.weights[start + i])
+    }
+
+    fn row(&self, id: NodeId) -> Neighbours<'_> {
+        match self.range(id) {
+            Some((start, end)) => Box::new(
+                self.targets[start..end]
+                    .iter()
+                    .copied()
+                    .zip(self.weights[start..end].iter().copied()),
+            ),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+}
+
+impl Storage for CompressedSparseRow {
+    fn add_node(&mut self, _: NodeId) -> Result<(), GraphError> {
+        Err(GraphError::Frozen)
+    }
+
+    fn remove_node(&mut self, _: NodeId) -> Result<(), GraphError> {
+        Err(GraphError::Frozen)
+    }
+
+    fn add_edge(&mut self, _: NodeId, _: NodeId, _: f64) -> Result<(), GraphError> {
+        Err(GraphError::Frozen)
+    }
+
+    fn remove_edge(&mut self, _: NodeId, _: NodeId) -> Result<Option<f64>, GraphError> {
+        Err(GraphError::Frozen)
+    }
+
+    fn weight(&self, from: NodeId, to: NodeId) -> Option<f64> {
+        self.outgoing.weight(from, to)
+    }
+
+    fn neighbours(&self, id: NodeId) -> Neighbours<'_> {
+        self.outgoing.row(id)
+    }
+
+    fn incoming(&self, id: NodeId) -> Neighbours<'_> {
+        self.incoming.row(id)
+    }
+
+    fn box_clone(&self) -> Box<dyn Storage> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,120 @@
+use crate::graph::node::NodeId;
+use crate::graph::storage::{Neighbours, Storage};
+use crate::graph::GraphError;
+
+// Each node's edges in a Vec, in the order they were added. Nodes refer to
+// each other by id, so there are no reference cycles to leak.
+#[derive(Debug, Clone, Default)]
+pub struct AdjacencyList {
+    nodes: Vec<Vec<(NodeId, f64)>>,
+    incoming: Vec<Vec<(NodeId, f64)>>,
+}
+
+// Associate functions
+impl AdjacencyList {
+    pub fn new() -> AdjacencyList {
+        AdjacencyList {
+            nodes: Vec::new(),
+            incoming: Vec::new(),
+        }
+    }
+
+    pub fn with_capacity(size: usize) -> AdjacencyList {
+        AdjacencyList {
+            nodes: Vec::with_capacity(size),
+            incoming: Vec::with_capacity(size),
+        }
+    }
+}
+
+impl Storage for AdjacencyList {
+    fn add_node(&mut self, id: NodeId) -> Result<(), GraphError> {
+        if self.nodes.len() <= id.index() {
+            self.nodes.resize_with(id.index() + 1, Vec::new);
+            self.incoming.resize_with(id.index() + 1, Vec::new);
+        }
+
+        Ok(())
+    }
+
+    fn remove_node(&mut self, id: NodeId) -> Result<(), GraphError> {
+        if id.index() >= self.nodes.len() {
+            return Ok(());
+        }
+
+        for (to, _) in std::mem::take(&mut self.nodes[id.index()]) {
+            remove(&mut self.incoming[to.index()], id);
+        }
+        for (from, _) in std::mem::take(&mut self.incoming[id.index()]) {
+            remove(&mut self.nodes[from.index()], id);
+        }
+
+        Ok(())
+    }
+
+    fn add_edge(&mut self, from: NodeId, to: NodeId, weight: f64) -> Result<(), GraphError> {
+        if from.index() >= self.nodes.len() {
+            return Err(GraphError::UnknownId(from));
+        }
+        if to.index() >= self.nodes.len() {
+            return Err(GraphError::UnknownId(to));
+        }
+
+        insert(&mut self.nodes[from.index()], to, weight);
+        insert(&mut self.incoming[to.index()], from, weight);
+
+        Ok(())
+    }
+
+    fn remove_edge(&mut self, from: NodeId, to: NodeId) -> Result<Option<f64>, GraphError> {
+        if let Some(edges) = self.incoming.get_mut(to.index()) {
+            remove(edges, from);
+        }
+
+        Ok(self
+            .nodes
+            .get_mut(from.index())
+            .and_then(|edges| remove(edges, to)))
+    }
+
+    fn weight(&self, from: NodeId, to: NodeId) -> Option<f64> {
+        self.nodes
+            .get(from.index())?
+            .iter()
+            .find(|(id, _)| *id == to)
+            .map(|(_, weight)| *weight)
+    }
+
+    fn neighbours(&self, id: NodeId) -> Neighbours<'_> {
+        match self.nodes.get(id.index()) {
+            Some(edges) => Box::new(edges.iter().copied()),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+
+    fn incoming(&self, id: NodeId) -> Neighbours<'_> {
+        match self.incoming.get(id.index()) {
+            Some(edges) => Box::new(edges.iter().copied()),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn Storage> {
+        Box::new(self.clone())
+    }
+}
+
+// Replaces the weight if there's already an edge to id.
+fn insert(edges: &mut Vec<(NodeId, f64)>, id: NodeId, weight: f64) {
+    match edges.iter_mut().find(|(other, _)| *other == id) {
+        Some(edge) => edge.1 = weight,
+        None => edges.push((id, weight)),
+    }
+}
+
+fn remove(edges: &mut Vec<(NodeId, f64)>, id: NodeId) -> Option<f64> {
+    edges
+        .iter()
+        .position(|(other, _)| *other == id)
+        .map(|i| edges.remove(i).1)
+}
@@ -0,0 +1,92 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::graph::node::NodeId;
+use crate::graph::storage::{Neighbours, Storage};
+use crate::graph::GraphError;
+
+#[derive(Debug, Clone, Default)]
+pub struct AdjacencyMap {
+    edges: HashMap<NodeId, BTreeMap<NodeId, f64>>,
+    incoming: HashMap<NodeId, BTreeMap<NodeId, f64>>,
+}
+
+// Associate functions
+impl AdjacencyMap {
+    pub fn new() -> AdjacencyMap {
+        AdjacencyMap {
+            edges: HashMap::new(),
+            incoming: HashMap::new(),
+        }
+    }
+}
+
+impl Storage for AdjacencyMap {
+    fn add_node(&mut self, id: NodeId) -> Result<(), GraphError> {
+        self.edges.insert(id, BTreeMap::new());
+        self.incoming.insert(id, BTreeMap::new());
+
+        Ok(())
+    }
+
+    fn remove_node(&mut self, id: NodeId) -> Result<(), GraphError> {
+        for (to, _) in self.edges.remove(&id).unwrap_or_default() {
+            if let Some(edges) = self.incoming.get_mut(&to) {
+                edges.remove(&id);
+            }
+        }
+        for (from, _) in self.incoming.remove(&id).unwrap_or_default() {
+            if let Some(edges) = self.edges.get_mut(&from) {
+                edges.remove(&id);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn add_edge(&mut self, from: NodeId, to: NodeId, weight: f64) -> Result<(), GraphError> {
+        if !self.incoming.contains_key(&to) {
+            return Err(GraphError::UnknownId(to));
+        }
+
+        self.edges
+            .get_mut(&from)
+            .ok_or(GraphError::UnknownId(from))?
+            .insert(to, weight);
+        self.incoming.get_mut(&to).unwrap().insert(from, weight);
+
+        Ok(())
+    }
+
+    fn remove_edge(&mut self, from: NodeId, to: NodeId) -> Result<Option<f64>, GraphError> {
+        if let Some(edges) = self.incoming.get_mut(&to) {
+            edges.remove(&from);
+        }
+
+        Ok(self
+            .edges
+            .get_mut(&from)
+            .and_then(|edges| edges.remove(&to)))
+    }
+
+    fn weight(&self, from: NodeId, to: NodeId) -> Option<f64> {
+        self.edges.get(&from)?.get(&to).copied()
+    }
+
+    fn neighbours(&self, id: NodeId) -> Neighbours<'_> {
+        match self.edges.get(&id) {
+            Some(edges) => Box::new(edges.iter().map(|(id, weight)| (*id, *weight))),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+
+    fn incoming(&self, id: NodeId) -> Neighbours<'_> {
+        match self.incoming.get(&id) {
+            Some(edges) => Box::new(edges.iter().map(|(id, weight)| (*id, *weight))),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn Storage> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,106 @@
+use crate::graph::node::NodeId;
+use crate::graph::storage::{Neighbours, Storage};
+use crate::graph::GraphError;
+
+// A row per node and a column per node, holding the weight of the edge
+// between them if there is one. Uses space for every pair of nodes, so it
+// suits small or densely connected graphs.
+#[derive(Debug, Clone, Default)]
+pub struct AdjacencyMatrix {
+    matrix: Vec<Vec<Option<f64>>>,
+}
+
+// Associate functions
+impl AdjacencyMatrix {
+    pub fn new() -> AdjacencyMatrix {
+        AdjacencyMatrix { matrix: Vec::new() }
+    }
+}
+
+// Public methods
+impl AdjacencyMatrix {
+    pub fn get_adjacency(&self, row: usize) -> &Vec<Option<f64>> {
+        &self.matrix[row]
+    }
+}
+
+impl Storage for AdjacencyMatrix {
+    fn add_node(&mut self, id: NodeId) -> Result<(), GraphError> {
+        let size = self.matrix.len().max(id.index() + 1);
+        for row in self.matrix.iter_mut() {
+            row.resize(size, None);
+        }
+        self.matrix.resize(size, vec![None; size]);
+
+        Ok(())
+    }
+
+    fn remove_node(&mut self, id: NodeId) -> Result<(), GraphError> {
+        if id.index() >= self.matrix.len() {
+            return Ok(());
+        }
+
+        for cell in self.matrix[id.index()].iter_mut() {
+            *cell = None;
+        }
+        for row in self.matrix.iter_mut() {
+            row[id.index()] = None;
+        }
+
+        Ok(())
+    }
+
+    fn add_edge(&mut self, from: NodeId, to: NodeId, weight: f64) -> Result<(), GraphError> {
+        let size = self.matrix.len();
+        if from.index() >= size {
+            return Err(GraphError::UnknownId(from));
+        }
+        if to.index() >= size {
+            return Err(GraphError::UnknownId(to));
+        }
+
+        self.matrix[from.index()][to.index()] = Some(weight);
+
+        Ok(())
+    }
+
+    fn remove_edge(&mut self, from: NodeId, to: NodeId) -> Result<Option<f64>, GraphError> {
+        Ok(self
+            .matrix
+            .get_mut(from.index())
+            .and_then(|row| row.get_mut(to.index()))
+            .and_then(Option::take))
+    }
+
+    fn weight(&self, from: NodeId, to: NodeId) -> Option<f64> {
+        *self.matrix.get(from.index())?.get(to.index())?
+    }
+
+    fn neighbours(&self, id: NodeId) -> Neighbours<'_> {
+        match self.matrix.get(id.index()) {
+            Some(row) => Box::new(
+                row.iter()
+                    .enumerate()
+                    .filter_map(|(i, weight)| weight.map(|weight| (NodeId::new(i), weight))),
+            ),
+            None => Box::new(std::iter::empty()),
+        }
+    }
+
+    fn incoming(&self, id: NodeId) -> Neighbours<'_> {
+        if id.index() >= self.matrix.len() {
+            return Box::new(std::iter::empty());
+        }
+
+        Box::new(
+            self.matrix
+                .iter()
+                .enumerate()
+                .filter_map(move |(i, row)| row[id.index()].map(|weight| (NodeId::new(i), weight))),
+        )
+    }
+
+    fn box_clone(&self) -> Box<dyn Storage> {
+        Box::new(self.clone())
+    }
+}
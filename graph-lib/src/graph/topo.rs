@@ -0,0 +1,95 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::graph::cycle;
+use crate::graph::node::NodeId;
+use crate::graph::{Graph, GraphError};
+
+// Kahn's algorithm: repeatedly take a node with no edges left coming in
+// from nodes not yet taken, so every edge points forward in the order.
+// Ties go to the lowest id. Fails with a cycle if nodes are left over,
+// since none of them can come first.
+pub fn sort(graph: &Graph) -> Result<Vec<NodeId>, GraphError> {
+    let mut incoming = vec![0; graph.node_bound()];
+    let mut queue = BinaryHeap::new();
+    for node in graph.nodes() {
+        incoming[node.id().index()] = graph.incoming(node.id()).count();
+        if incoming[node.id().index()] == 0 {
+            queue.push(Reverse(node.id()));
+        }
+    }
+
+    let mut order = Vec::with_capacity(graph.len());
+    while let Some(Reverse(id)) = queue.pop() {
+        order.push(id);
+
+        for (other_id, _) in graph.edges(id) {
+            incoming[other_id.index()] -= 1;
+            if incoming[other_id.index()] == 0 {
+                queue.push(Reverse(other_id));
+            }
+        }
+    }
+
+    if order.len() < graph.len() {
+        let cycle =
+            cycle::find_directed(graph).expect("nodes left over should be held back by a cycle");
+        return Err(GraphError::Cycle(cycle));
+    }
+
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::node::NodeId;
+    use crate::graph::point::Point;
+    use crate::graph::{Graph, GraphError};
+
+    fn dag(edges: &[(usize, usize)]) -> (Graph, Vec<NodeId>) {
+        let mut graph = Graph::new();
+        let ids: Vec<_> = (0..6)
+            .map(|i| {
+                graph
+                    .add_node(&i.to_string(), Point::new(0.0, 0.0))
+                    .unwrap()
+            })
+            .collect();
+        for (from, to) in edges.iter() {
+            graph
+                .add_directed_weighted_edge(ids[*from], ids[*to], 1.0)
+                .unwrap();
+        }
+
+        (graph, ids)
+    }
+
+    #[test]
+    fn every_edge_points_forward() {
+        let (graph, ids) = dag(&[(5, 2), (5, 0), (4, 0), (4, 1), (2, 3), (3, 1)]);
+        let order = graph.topological_sort().unwrap();
+
+        assert_eq!(order.len(), ids.len());
+        let position = |id: NodeId| order.iter().position(|other| *other == id).unwrap();
+        for id in ids.iter() {
+            for (other_id, _) in graph.edges(*id) {
+                assert!(position(*id) < position(other_id));
+            }
+        }
+        // Ties go to the lowest id.
+        assert_eq!(order, vec![ids[4], ids[5], ids[0], ids[2], ids[3], ids[1]]);
+    }
+
+    #[test]
+    fn fails_with_the_cycle() {
+        let (graph, ids) = dag(&[(0, 1), (1, 2), (2, 3), (3, 1), (3, 4)]);
+
+        match graph.topological_sort() {
+            Err(GraphError::Cycle(mut cycle)) => {
+                cycle.sort();
+                assert_eq!(cycle, vec![ids[1], ids[2], ids[3]]);
+            }
+            other => panic!("expected a cycle, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use ordered_float::NotNan;
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::graph::node::NodeId;
+use crate::graph::search::make_path;
+use crate::graph::{Graph, GraphError};
+
+// The shortest path from one source to every node it can reach, from a
+// single run of Dijkstra's algorithm.
+#[derive(Debug, Clone)]
+pub struct ShortestPathTree {
+    source: NodeId,
+    visited: HashMap<NodeId, (Option<NodeId>, f64)>,
+}
+
+// Associate functions
+impl ShortestPathTree {
+    pub fn new(graph: &Graph, source: NodeId) -> Result<ShortestPathTree, GraphError> {
+        graph.node(source)?;
+
+        Ok(ShortestPathTree {
+            source,
+            visited: dijkstra(graph, source),
+        })
+    }
+}
+
+// Public methods
+impl ShortestPathTree {
+    pub fn source(&self) -> NodeId {
+        self.source
+    }
+
+    pub fn is_reachable(&self, id: NodeId) -> bool {
+        self.visited.contains_key(&id)
+    }
+
+    pub fn distance(&self, id: NodeId) -> Option<f64> {
+        self.visited.get(&id).map(|(_, dist)| *dist)
+    }
+
+    // The node before id on its shortest path, or None for the source and
+    // for unreachable nodes.
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.visited.get(&id)?.0
+    }
+
+    pub fn path(&self, id: NodeId) -> Option<Vec<(NodeId, f64)>> {
+        if !self.is_reachable(id) {
+            return None;
+        }
+
+        Some(make_path(&self.visited, id))
+    }
+
+    // Every reachable node with its distance from the source, nearest first.
+    pub fn distances(&self) -> Vec<(NodeId, f64)> {
+        let mut distances: Vec<(NodeId, f64)> = self
+            .visited
+            .iter()
+            .map(|(id, (_, dist))| (*id, *dist))
+            .collect();
+        distances.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap().then(a.0.cmp(&b.0)));
+
+        distances
+    }
+}
+
+// Runs until the queue is empty rather than stopping at a destination.
+pub(crate) fn dijkstra(graph: &Graph, source: NodeId) -> HashMap<NodeId, (Option<NodeId>, f64)> {
+    let mut visited = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    queue.push(Reverse((NotNan::new(0.0).unwrap(), source, None)));
+
+    while let Some(Reverse((dist, id, from))) = queue.pop() {
+        if visited.contains_key(&id) {
+            continue;
+        }
+        visited.insert(id, (from, *dist));
+
+        for (other_id, weight) in graph.edges(id) {
+            if visited.contains_key(&other_id) {
+                continue;
+            }
+            let dist = NotNan::new(*dist + weight).unwrap();
+            queue.push(Reverse((dist, other_id, Some(id))));
+        }
+    }
+
+    visited
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::point::Point;
+    use crate::graph::{Graph, GraphError};
+
+    #[test]
+    fn matches_point_to_point_searches() {
+        let mut graph = Graph::new();
+        let ids: Vec<_> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|name| graph.add_node(name, Point::new(0.0, 0.0)).unwrap())
+            .collect();
+        graph.add_weighted_edge(ids[0], ids[1], 4.0).unwrap();
+        graph.add_weighted_edge(ids[0], ids[2], 1.0).unwrap();
+        graph.add_weighted_edge(ids[2], ids[1], 2.0).unwrap();
+        graph
+            .add_directed_weighted_edge(ids[1], ids[3], 5.0)
+            .unwrap();
+
+        let tree = graph.shortest_path_tree(ids[0]).unwrap();
+        for id in ids[..4].iter() {
+            assert_eq!(tree.path(*id), graph.shortest_path(ids[0], *id).unwrap());
+        }
+        assert_eq!(tree.parent(ids[1]), Some(ids[2]));
+        assert_eq!(tree.parent(ids[0]), None);
+        assert_eq!(
+            tree.distances(),
+            vec![(ids[0], 0.0), (ids[2], 1.0), (ids[1], 3.0), (ids[3], 8.0)]
+        );
+        assert!(!tree.is_reachable(ids[4]));
+        assert_eq!(tree.path(ids[4]), None);
+    }
+
+    #[test]
+    fn rejects_unknown_source() {
+        let graph = Graph::new();
+        let id = crate::graph::node::NodeId::new(0);
+
+        assert_eq!(
+            graph.shortest_path_tree(id).err(),
+            Some(GraphError::UnknownId(id))
+        );
+    }
+}
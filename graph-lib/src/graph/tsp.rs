@@ -0,0 +1,424 @@
+use crate::graph::node::NodeId;
+use crate::graph::tree::ShortestPathTree;
+use crate::graph::{Graph, GraphError};
+
+// Held-Karp keeps a table entry for every subset of the stops, so it's
+// limited to this many.
+pub const HELD_KARP_LIMIT: usize = 16;
+
+// How far apart two costs can be and still count as the same, since the
+// shortest path each way adds the same weights in a different order.
+const TOLERANCE: f64 = 1e-9;
+
+// A trip through a list of stops, with the shortest path for each leg.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tour {
+    stops: Vec<NodeId>,
+    legs: Vec<Vec<(NodeId, f64)>>,
+    total: f64,
+}
+
+// Public methods
+impl Tour {
+    // In the order they're visited. A round trip doesn't repeat the first
+    // stop at the end.
+    pub fn stops(&self) -> &[NodeId] {
+        &self.stops
+    }
+
+    // The path from each stop to the next, with distances counted from the
+    // start of the leg.
+    pub fn legs(&self) -> &[Vec<(NodeId, f64)>] {
+        &self.legs
+    }
+
+    pub fn leg_totals(&self) -> Vec<f64> {
+        self.legs
+            .iter()
+            .map(|leg| leg.last().map_or(0.0, |(_, dist)| *dist))
+            .collect()
+    }
+
+    pub fn total(&self) -> f64 {
+        self.total
+    }
+
+    // Every leg joined into one path, with distances counted from the
+    // first stop.
+    pub fn path(&self) -> Vec<(NodeId, f64)> {
+        let mut path: Vec<(NodeId, f64)> = self.stops.iter().take(1).map(|id| (*id, 0.0)).collect();
+        let mut so_far = 0.0;
+
+        for leg in self.legs.iter() {
+            path.extend(leg[1..].iter().map(|(id, dist)| (*id, so_far + dist)));
+            so_far += leg.last().map_or(0.0, |(_, dist)| *dist);
+        }
+
+        path
+    }
+}
+
+// Shortest paths between every pair of stops, from one Dijkstra run per
+// stop. Unreachable pairs cost infinity.
+#[derive(Debug)]
+pub(crate) struct Costs {
+    stops: Vec<NodeId>,
+    trees: Vec<ShortestPathTree>,
+    symmetric: bool,
+}
+
+impl Costs {
+    pub(crate) fn new(graph: &Graph, stops: &[NodeId]) -> Result<Costs, GraphError> {
+        let trees = stops
+            .iter()
+            .map(|id| ShortestPathTree::new(graph, *id))
+            .collect::<Result<_, _>>()?;
+
+        let mut costs = Costs {
+            stops: stops.to_vec(),
+            trees,
+            symmetric: false,
+        };
+        costs.symmetric =
+            (0..stops.len()).all(|i| (0..i).all(|j| same_cost(costs.cost(i, j), costs.cost(j, i))));
+
+        Ok(costs)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.stops.len()
+    }
+
+    pub(crate) fn cost(&self, from: usize, to: usize) -> f64 {
+        self.trees[from]
+            .distance(self.stops[to])
+            .unwrap_or(f64::INFINITY)
+    }
+
+    fn order_cost(&self, order: &[usize], closed: bool) -> f64 {
+        let legs: f64 = order.windows(2).map(|leg| self.cost(leg[0], leg[1])).sum();
+
+        match (closed, order.first(), order.last()) {
+            (true, Some(first), Some(last)) if order.len() > 1 => legs + self.cost(*last, *first),
+            _ => legs,
+        }
+    }
+
+    // Builds the tour for an order of stop indexes, failing on the first
+    // leg that has no path.
+    pub(crate) fn tour(&self, order: &[usize], closed: bool) -> Result<Tour, GraphError> {
+        let mut visits = order.to_vec();
+        if closed && order.len() > 1 {
+            visits.push(order[0]);
+        }
+
+        let mut legs = Vec::with_capacity(visits.len().saturating_sub(1));
+        for leg in visits.windows(2) {
+            let (from, to) = (self.stops[leg[0]], self.stops[leg[1]]);
+            let path = self.trees[leg[0]]
+                .path(to)
+                .ok_or(GraphError::NoPath(from, to))?;
+            legs.push(path);
+        }
+
+        Ok(Tour {
+            stops: order.iter().map(|i| self.stops[*i]).collect(),
+            legs,
+            total: self.order_cost(order, closed),
+        })
+    }
+}
+
+// Drops repeats of a stop after the first, which a round trip has no use
+// for.
+pub(crate) fn unique(stops: &[NodeId]) -> Vec<NodeId> {
+    let mut unique: Vec<NodeId> = Vec::with_capacity(stops.len());
+    for id in stops {
+        if !unique.contains(id) {
+            unique.push(*id);
+        }
+    }
+
+    unique
+}
+
+// The orders below are lists of stop indexes that always begin with 0.
+// A closed order returns to 0 at the end; an open one must finish on the
+// last stop instead.
+
+// Dynamic programming over subsets: the cheapest way to start at 0, visit
+// every stop in a set and end on a given one, built up from smaller sets.
+pub(crate) fn held_karp(costs: &Costs, closed: bool) -> Result<Vec<usize>, GraphError> {
+    let n = costs.len();
+    if n > HELD_KARP_LIMIT {
+        return Err(GraphError::TooManyStops(n));
+    }
+    if n <= 2 {
+        return Ok((0..n).collect());
+    }
+
+    // Stop 0 is always first, so sets only cover stops 1 to n - 1, with
+    // stop i as bit i - 1.
+    let sets = 1 << (n - 1);
+    let mut best = vec![vec![f64::INFINITY; n]; sets];
+    let mut prev = vec![vec![0; n]; sets];
+    for i in 1..n {
+        best[1 << (i - 1)][i] = costs.cost(0, i);
+    }
+
+    for set in 1..sets {
+        for last in 1..n {
+            if set & (1 << (last - 1)) == 0 || best[set][last].is_infinite() {
+                continue;
+            }
+            for next in 1..n {
+                if set & (1 << (next - 1)) != 0 {
+                    continue;
+                }
+
+                let with_next = set | (1 << (next - 1));
+                let cost = best[set][last] + costs.cost(last, next);
+                if cost < best[with_next][next] {
+                    best[with_next][next] = cost;
+                    prev[with_next][next] = last;
+                }
+            }
+        }
+    }
+
+    let all = sets - 1;
+    let mut last = if closed {
+        (1..n)
+            .min_by(|a, b| {
+                let a = best[all][*a] + costs.cost(*a, 0);
+                let b = best[all][*b] + costs.cost(*b, 0);
+                a.partial_cmp(&b).unwrap()
+            })
+            .unwrap()
+    } else {
+        n - 1
+    };
+
+    // No order reaches every stop. Any order will do for tour() to report
+    // a leg without a path.
+    let total = best[all][last] + if closed { costs.cost(last, 0) } else { 0.0 };
+    if total.is_infinite() {
+        return Ok((0..n).collect());
+    }
+
+    let mut order = Vec::with_capacity(n);
+    let mut set = all;
+    while last != 0 {
+        order.push(last);
+        let before = prev[set][last];
+        set &= !(1 << (last - 1));
+        last = before;
+    }
+    order.push(0);
+    order.reverse();
+
+    Ok(order)
+}
+
+// Always heads for the closest stop not yet visited.
+pub(crate) fn nearest_neighbour(costs: &Costs, closed: bool) -> Vec<usize> {
+    let n = costs.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let end = if closed || n == 1 { None } else { Some(n - 1) };
+    let mut left: Vec<usize> = (1..n).filter(|i| Some(*i) != end).collect();
+    let mut order = vec![0];
+
+    while !left.is_empty() {
+        let last = *order.last().unwrap();
+        let (i, _) = left
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| {
+                costs
+                    .cost(last, **a)
+                    .partial_cmp(&costs.cost(last, **b))
+                    .unwrap()
+            })
+            .unwrap();
+        order.push(left.remove(i));
+    }
+    order.extend(end);
+
+    order
+}
+
+// Keeps reversing a stretch of the order while that makes it cheaper. The
+// first stop, and the last on an open order, stay where they are. When
+// every leg costs the same both ways, only the two legs at the ends of the
+// stretch change, so each try is O(1) rather than a walk of the order.
+pub(crate) fn two_opt(costs: &Costs, mut order: Vec<usize>, closed: bool) -> Vec<usize> {
+    let len = order.len();
+    let end = if closed { len } else { len.saturating_sub(1) };
+    let mut best = costs.order_cost(&order, closed);
+
+    let mut improved = true;
+    while improved {
+        improved = false;
+
+        for i in 1..end {
+            for j in i + 1..end {
+                if costs.symmetric {
+                    let (a, b) = (order[i - 1], order[i]);
+                    let (c, d) = (order[j], order[(j + 1) % len]);
+                    let delta =
+                        costs.cost(a, c) + costs.cost(b, d) - costs.cost(a, b) - costs.cost(c, d);
+                    if delta < -TOLERANCE {
+                        order[i..=j].reverse();
+                        improved = true;
+                    }
+                    continue;
+                }
+
+                order[i..=j].reverse();
+                let cost = costs.order_cost(&order, closed);
+                if cost < best {
+                    best = cost;
+                    improved = true;
+                } else {
+                    order[i..=j].reverse();
+                }
+            }
+        }
+    }
+
+    order
+}
+
+fn same_cost(a: f64, b: f64) -> bool {
+    a == b || (a - b).abs() <= TOLERANCE * a.abs().max(b.abs()).max(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::point::Point;
+
+    // Every pair joined, with weights that don't favour any obvious order.
+    fn complete(len: usize, directed: bool) -> (Graph, Vec<NodeId>) {
+        let mut graph = Graph::new();
+        let ids: Vec<_> = (0..len)
+            .map(|i| {
+                graph
+                    .add_node(&i.to_string(), Point::new(0.0, 0.0))
+                    .unwrap()
+            })
+            .collect();
+        let mut seed = 11;
+        for i in 0..len {
+            for j in 0..len {
+                if i == j || (!directed && j < i) {
+                    continue;
+                }
+                seed = (seed * 37 + 23) % 101;
+                let weight = 1.0 + seed as f64;
+                if directed {
+                    graph
+                        .add_directed_weighted_edge(ids[i], ids[j], weight)
+                        .unwrap();
+                } else {
+                    graph.add_weighted_edge(ids[i], ids[j], weight).unwrap();
+                }
+            }
+        }
+
+        (graph, ids)
+    }
+
+    fn cheapest_by_brute_force(costs: &Costs, order: &mut Vec<usize>, at: usize) -> f64 {
+        if at == order.len() {
+            return costs.order_cost(order, true);
+        }
+
+        let mut best = f64::INFINITY;
+        for i in at..order.len() {
+            order.swap(at, i);
+            best = best.min(cheapest_by_brute_force(costs, order, at + 1));
+            order.swap(at, i);
+        }
+
+        best
+    }
+
+    #[test]
+    fn held_karp_matches_brute_force() {
+        for directed in [false, true].iter() {
+            let (graph, ids) = complete(7, *directed);
+            let costs = Costs::new(&graph, &ids).unwrap();
+            let mut order: Vec<usize> = (0..ids.len()).collect();
+            let best = cheapest_by_brute_force(&costs, &mut order, 1);
+
+            let tour = graph.held_karp_tour(&ids).unwrap();
+            assert!((tour.total() - best).abs() < 1e-9);
+            assert_eq!(tour.stops()[0], ids[0]);
+            assert_eq!(tour.stops().len(), ids.len());
+        }
+    }
+
+    #[test]
+    fn two_opt_leaves_no_improving_reversal() {
+        for directed in [false, true].iter() {
+            let (graph, ids) = complete(9, *directed);
+            let costs = Costs::new(&graph, &ids).unwrap();
+            assert_eq!(costs.symmetric, !*directed);
+
+            let start = nearest_neighbour(&costs, true);
+            let mut order = two_opt(&costs, start.clone(), true);
+            let cost = costs.order_cost(&order, true);
+            assert!(cost <= costs.order_cost(&start, true));
+            assert_eq!(order[0], 0);
+
+            for i in 1..order.len() {
+                for j in i + 1..order.len() {
+                    order[i..=j].reverse();
+                    assert!(costs.order_cost(&order, true) >= cost - 1e-9);
+                    order[i..=j].reverse();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reordered_via_route_keeps_its_ends() {
+        let (graph, ids) = complete(6, false);
+        let route = graph.via_route_reordered(&ids).unwrap();
+
+        assert_eq!(route.stops().first(), ids.first());
+        assert_eq!(route.stops().last(), ids.last());
+        assert!(route.total() <= graph.via_route(&ids).unwrap().total());
+    }
+
+    #[test]
+    fn via_route_joins_shortest_legs() {
+        let mut graph = Graph::new();
+        let ids: Vec<_> = ["a", "b", "c", "d"]
+            .iter()
+            .map(|name| graph.add_node(name, Point::new(0.0, 0.0)).unwrap())
+            .collect();
+        for i in 0..3 {
+            graph
+                .add_weighted_edge(ids[i], ids[i + 1], 1.0 + i as f64)
+                .unwrap();
+        }
+
+        let route = graph.via_route(&[ids[0], ids[3], ids[1]]).unwrap();
+        assert_eq!(route.leg_totals(), vec![6.0, 5.0]);
+        assert_eq!(route.total(), 11.0);
+        let nodes: Vec<NodeId> = route.path().iter().map(|(id, _)| *id).collect();
+        assert_eq!(nodes, vec![ids[0], ids[1], ids[2], ids[3], ids[2], ids[1]]);
+        assert_eq!(route.path().last().unwrap().1, 11.0);
+
+        let lonely = graph.add_node("e", Point::new(0.0, 0.0)).unwrap();
+        assert_eq!(
+            graph.via_route(&[ids[0], lonely]).err(),
+            Some(GraphError::NoPath(ids[0], lonely))
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use crate::graph::node::NodeId;
+use crate::graph::search::dijk::ShortestPath;
+use crate::graph::search::{Search, SearchOptions, Status};
+use crate::graph::{Graph, GraphError};
+
+type Path = Vec<(NodeId, f64)>;
+
+// Yen's algorithm. Each new path leaves an earlier one at some spur node,
+// following the earlier path up to there and then the shortest way on to
+// the end that avoids the nodes already used and the edges taken out of
+// the spur by paths sharing the same start.
+pub fn k_shortest_paths(
+    graph: &Graph,
+    start: NodeId,
+    end: NodeId,
+    k: usize,
+) -> Result<Vec<Path>, GraphError> {
+    let mut paths: Vec<Path> = Vec::with_capacity(k);
+    let mut candidates: Vec<Path> = Vec::new();
+
+    match shortest(graph, start, end, SearchOptions::new())? {
+        Some(path) if k > 0 => paths.push(path),
+        _ => return Ok(paths),
+    }
+
+    while paths.len() < k {
+        let last = paths.last().unwrap();
+
+        for i in 0..last.len() - 1 {
+            let (spur, root_dist) = last[i];
+            let root = &last[..=i];
+
+            let options = paths
+                .iter()
+                .filter(|path| path.len() > i + 1 && same_nodes(&path[..=i], root))
+                .fold(SearchOptions::new(), |options, path| {
+                    options.with_forbidden_directed_edge(path[i].0, path[i + 1].0)
+                });
+            let options = root[..i]
+                .iter()
+                .fold(options, |options, (id, _)| options.with_forbidden_node(*id));
+
+            let spur_path = match shortest(graph, spur, end, options)? {
+                Some(spur_path) => spur_path,
+                None => continue,
+            };
+
+            let mut path = root.to_vec();
+            path.extend(
+                spur_path[1..]
+                    .iter()
+                    .map(|(id, dist)| (*id, root_dist + dist)),
+            );
+
+            let known = |other: &Path| same_nodes(other, &path);
+            if !paths.iter().any(known) && !candidates.iter().any(known) {
+                candidates.push(path);
+            }
+        }
+
+        // The cheapest candidate, taking the one found first on a tie.
+        let next = candidates
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| cost(a).partial_cmp(&cost(b)).unwrap())
+            .map(|(i, _)| i);
+
+        match next {
+            Some(i) => paths.push(candidates.remove(i)),
+            None => break,
+        }
+    }
+
+    Ok(paths)
+}
+
+fn shortest(
+    graph: &Graph,
+    start: NodeId,
+    end: NodeId,
+    options: SearchOptions,
+) -> Result<Option<Path>, GraphError> {
+    let mut search = ShortestPath::with_options(graph, start, end, options)?;
+
+    while let Status::Searching = search.next() {}
+
+    Ok(search.result())
+}
+
+fn cost(path: &[(NodeId, f64)]) -> f64 {
+    path.last().map_or(0.0, |(_, dist)| *dist)
+}
+
+fn same_nodes(a: &[(NodeId, f64)], b: &[(NodeId, f64)]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.0 == b.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::point::Point;
+
+    // The example from the Wikipedia article on Yen's algorithm.
+    fn example() -> (Graph, Vec<NodeId>) {
+        let mut graph = Graph::new();
+        let ids: Vec<_> = ["C", "D", "E", "F", "G", "H"]
+            .iter()
+            .map(|name| graph.add_node(name, Point::new(0.0, 0.0)).unwrap())
+            .collect();
+        let edges = [
+            (0, 1, 3.0),
+            (0, 2, 2.0),
+            (1, 3, 4.0),
+            (2, 1, 1.0),
+            (2, 3, 2.0),
+            (2, 4, 3.0),
+            (3, 4, 2.0),
+            (3, 5, 1.0),
+            (4, 5, 2.0),
+        ];
+        for (from, to, weight) in edges.iter() {
+            graph
+                .add_directed_weighted_edge(ids[*from], ids[*to], *weight)
+                .unwrap();
+        }
+
+        (graph, ids)
+    }
+
+    fn simple_path_costs(graph: &Graph, path: &mut Vec<NodeId>, end: NodeId, costs: &mut Vec<f64>) {
+        let last = *path.last().unwrap();
+        if last == end {
+            let cost = path
+                .windows(2)
+                .map(|leg| graph.weight(leg[0], leg[1]).unwrap())
+                .sum();
+            costs.push(cost);
+            return;
+        }
+
+        for (id, _) in graph.edges(last) {
+            if !path.contains(&id) {
+                path.push(id);
+                simple_path_costs(graph, path, end, costs);
+                path.pop();
+            }
+        }
+    }
+
+    #[test]
+    fn finds_the_example_paths_in_order() {
+        let (graph, ids) = example();
+        let paths = graph.k_shortest_paths(ids[0], ids[5], 3).unwrap();
+
+        let nodes: Vec<Vec<NodeId>> = paths
+            .iter()
+            .map(|path| path.iter().map(|(id, _)| *id).collect())
+            .collect();
+        assert_eq!(
+            nodes,
+            vec![
+                vec![ids[0], ids[2], ids[3], ids[5]],
+                vec![ids[0], ids[2], ids[4], ids[5]],
+                vec![ids[0], ids[1], ids[3], ids[5]],
+            ]
+        );
+        let totals: Vec<f64> = paths.iter().map(|path| path.last().unwrap().1).collect();
+        assert_eq!(totals, vec![5.0, 7.0, 8.0]);
+    }
+
+    #[test]
+    fn lists_every_simple_path_cheapest_first() {
+        let (graph, ids) = example();
+        let mut costs = Vec::new();
+        simple_path_costs(&graph, &mut vec![ids[0]], ids[5], &mut costs);
+        costs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let paths = graph.k_shortest_paths(ids[0], ids[5], 100).unwrap();
+        let totals: Vec<f64> = paths.iter().map(|path| path.last().unwrap().1).collect();
+        assert_eq!(totals, costs);
+        assert!(graph
+            .k_shortest_paths(ids[5], ids[0], 3)
+            .unwrap()
+            .is_empty());
+        assert!(graph
+            .k_shortest_paths(ids[0], ids[5], 0)
+            .unwrap()
+            .is_empty());
+    }
+}
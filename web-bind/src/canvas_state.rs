@@ -12,6 +12,9 @@ pub struct CanvasState {
     active: Option<String>,
     searched: HashSet<String>,
     path: HashSet<String>,
+    // Spanning trees are drawn by edge rather than by city.
+    frontier: HashSet<Conn>,
+    tree: HashSet<Conn>,
     changed_since_last_draw: RefCell<bool>,
 }
 
@@ -26,6 +29,8 @@ impl CanvasState {
             active: None,
             searched: HashSet::new(),
             path: HashSet::new(),
+            frontier: HashSet::new(),
+            tree: HashSet::new(),
             changed_since_last_draw: RefCell::new(true),
         }
     }
@@ -111,6 +116,28 @@ impl CanvasState {
         }
     }
 
+    pub fn frontier(&self) -> &HashSet<Conn> {
+        &self.frontier
+    }
+
+    pub fn set_frontier(&mut self, frontier: HashSet<Conn>) {
+        if self.frontier != frontier {
+            self.frontier = frontier;
+            self.changed_since_last_draw.replace(true);
+        }
+    }
+
+    pub fn tree(&self) -> &HashSet<Conn> {
+        &self.tree
+    }
+
+    pub fn set_tree(&mut self, tree: HashSet<Conn>) {
+        if self.tree != tree {
+            self.tree = tree;
+            self.changed_since_last_draw.replace(true);
+        }
+    }
+
     pub fn changed_since_last_draw(&self) -> bool {
         *self.changed_since_last_draw.borrow()
     }
@@ -120,6 +147,8 @@ impl CanvasState {
         self.searched.clear();
         self.queued.clear();
         self.path.clear();
+        self.frontier.clear();
+        self.tree.clear();
 
         self.changed_since_last_draw.replace(true);
     }
@@ -141,6 +170,10 @@ impl CanvasState {
                 && (self.hovered.as_ref().unwrap() == src || self.hovered.as_ref().unwrap() == dest)
             {
                 context.set_stroke_style(&consts::HOVERED_CONN_COLOR.into());
+            } else if has_conn(&self.tree, src, dest) {
+                context.set_stroke_style_str(consts::PATH_CONN_COLOR);
+            } else if has_conn(&self.frontier, src, dest) {
+                context.set_stroke_style_str(consts::QUEUED_CONN_COLOR);
             } else if self.path.contains(src) && self.path.contains(dest) {
                 context.set_stroke_style(&consts::PATH_CONN_COLOR.into());
             } else if self.searched.contains(src) && self.searched.contains(dest) {
@@ -191,3 +224,9 @@ impl CanvasState {
         self.changed_since_last_draw.replace(false);
     }
 }
+
+// Connections are drawn once, whichever way round they're stored.
+fn has_conn(conns: &HashSet<Conn>, src: &str, dest: &str) -> bool {
+    conns.contains(&Conn(src.to_owned(), dest.to_owned()))
+        || conns.contains(&Conn(dest.to_owned(), src.to_owned()))
+}
@@ -1,5 +1,6 @@
 pub const DEFAULT_SPEED: f64 = 250.0;
 pub const DOT_RADIUS: f64 = 5.0;
+pub const BEAM_WIDTH: usize = 3;
 
 pub const CONN_COLOR: &str = "#f33";
 pub const CITY_COLOR: &str = "#000";
@@ -10,8 +10,9 @@ use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use web_sys::console;
 
+use graph_lib::graph::mst::{SpanningTree, TreeEdge};
 use graph_lib::graph::search::{Search, Status};
-use graph_lib::graph::Graph;
+use graph_lib::graph::{Graph, GraphError};
 use graph_lib::{gstring_parse, make_graph};
 
 use canvas_state::CanvasState;
@@ -57,9 +58,10 @@ pub fn run() -> Result<(), JsValue> {
         .set_attribute("height", &canvas_height.to_string())
         .unwrap();
 
-    let graph = make_graph();
-    let graph = Rc::new(graph);
-    let (cities, connections) = map_nodes(&graph, canvas_height, canvas_width);
+    // Searches borrow the graph, and it lives as long as the page.
+    // leaks memory
+    let graph: &'static Graph = Box::leak(Box::new(make_graph()));
+    let (cities, connections) = map_nodes(graph, canvas_height, canvas_width);
     let canvas_state = Rc::new(RefCell::new(CanvasState::new(cities, connections)));
 
     make_color_key(&document)?;
@@ -85,7 +87,7 @@ pub fn run() -> Result<(), JsValue> {
         Rc::clone(&window),
         Rc::clone(&canvas),
         Rc::clone(&context),
-        Rc::clone(&graph),
+        graph,
         Rc::clone(&canvas_state),
     );
     window.add_event_listener_with_callback(
@@ -111,7 +113,7 @@ pub fn run() -> Result<(), JsValue> {
         Rc::clone(&document),
         Rc::clone(&canvas),
         Rc::clone(&context),
-        Rc::clone(&graph),
+        graph,
         Rc::clone(&canvas_state),
         Rc::clone(&is_searching),
         Rc::clone(&search_stop),
@@ -167,18 +169,20 @@ fn map_nodes(graph: &Graph, height: f64, width: f64) -> (HashMap<String, City>,
     let width_ratio = width / (us_east - us_west);
     let width_offset = us_west;
 
-    let mut mapped_nodes: HashMap<String, City> = HashMap::with_capacity(graph.nodes().len());
+    let mut mapped_nodes: HashMap<String, City> = HashMap::with_capacity(graph.len());
     let mut connections: HashSet<Conn> = HashSet::new();
 
-    for (id, node) in graph.nodes().iter() {
+    for node in graph.nodes() {
+        let id = graph.name(node.id()).unwrap();
         let row = (node.point().row() - height_offset) * height_ratio * -1.0;
         let row = (row + (height_pad / 2.0)).round();
         let col = (node.point().col() - width_offset) * width_ratio;
         let col = (col + (width_pad / 2.0)).round();
 
-        mapped_nodes.insert(id.clone(), City::new(col, row, DOT_RADIUS));
+        mapped_nodes.insert(id.to_owned(), City::new(col, row, DOT_RADIUS));
 
-        for (dest, _) in node.edges().iter() {
+        for (dest, _) in graph.edges(node.id()) {
+            let dest = graph.name(dest).unwrap();
             let conn = Conn(dest.to_owned(), id.to_owned());
             if connections.contains(&conn) {
                 continue;
@@ -475,7 +479,7 @@ fn make_window_resize_listener(
     window: Rc<web_sys::Window>,
     canvas: Rc<web_sys::HtmlCanvasElement>,
     context: Rc<web_sys::CanvasRenderingContext2d>,
-    graph: Rc<Graph>,
+    graph: &'static Graph,
     canvas_state: Rc<RefCell<CanvasState>>,
 ) -> Closure<(dyn FnMut(web_sys::Event) + 'static)> {
     Closure::wrap(Box::new(move |_| {
@@ -489,7 +493,7 @@ fn make_window_resize_listener(
             .set_attribute("height", &canvas_height.to_string())
             .unwrap();
 
-        let (cities, connections) = map_nodes(&graph, canvas_height, canvas_width);
+        let (cities, connections) = map_nodes(graph, canvas_height, canvas_width);
         canvas_state.borrow_mut().set_cities(cities);
         canvas_state.borrow_mut().set_connections(connections);
 
@@ -502,7 +506,7 @@ fn make_search_button_listener(
     document: Rc<web_sys::Document>,
     canvas: Rc<web_sys::HtmlCanvasElement>,
     context: Rc<web_sys::CanvasRenderingContext2d>,
-    graph: Rc<Graph>,
+    graph: &'static Graph,
     canvas_state: Rc<RefCell<CanvasState>>,
     is_searching: Rc<RefCell<bool>>,
     search_stop: Rc<RefCell<bool>>,
@@ -550,53 +554,93 @@ fn make_search_button_listener(
             .dyn_into::<web_sys::HtmlElement>()
             .map_err(|_| ())
             .unwrap();
+        let progress_text = document
+            .get_element_by_id("search-progress-text")
+            .expect("document should contain search-progress-text element");
+        let progress_text = progress_text
+            .dyn_into::<web_sys::HtmlElement>()
+            .map_err(|_| ())
+            .unwrap();
+
+        // Spanning trees cover the whole map, so they only need a start,
+        // and Kruskal's doesn't even need that.
+        if search_type_value == "prim" || search_type_value == "kruskal" {
+            let tree = if search_type_value == "prim" {
+                graph
+                    .id(&src_in_value)
+                    .and_then(|src| box_spanning_tree(graph.step_prim(src)))
+            } else {
+                box_spanning_tree(Ok(graph.step_kruskal()))
+            };
+
+            let tree = match tree {
+                Ok(tree) => {
+                    error_text.style().set_property("display", "none").unwrap();
+                    progress_text.set_inner_html("");
+                    tree
+                }
+                Err(_) => {
+                    error_text
+                        .style()
+                        .set_property("display", "inline")
+                        .unwrap();
+                    return;
+                }
+            };
 
-        if !graph.nodes().contains_key(&src_in_value) || !graph.nodes().contains_key(&dest_in_value)
-        {
-            error_text
-                .style()
-                .set_property("display", "inline")
-                .unwrap();
+            // Stop current searches
+            if *is_searching.borrow() {
+                search_stop.replace(true);
+            }
+            is_searching.replace(true);
+            canvas_state.borrow_mut().reset();
+
+            animate_spanning_tree(
+                Rc::clone(&window),
+                Rc::clone(&performance),
+                Board {
+                    canvas: Rc::clone(&canvas),
+                    context: Rc::clone(&context),
+                    state: Rc::clone(&canvas_state),
+                },
+                graph,
+                Rc::clone(&is_searching),
+                Rc::clone(&search_stop),
+                tree,
+            );
             return;
-        } else {
-            error_text.style().set_property("display", "none").unwrap();
         }
 
-        let search: Box<RefCell<Search>>;
-
-        match search_type_value.as_ref() {
-            "dfs" => {
-                search = Box::new(RefCell::new(
-                    (*graph)
-                        .clone()
-                        .step_depth_first_search(&src_in_value, &dest_in_value),
-                ));
-            }
-            "bfs" => {
-                search = Box::new(RefCell::new(
-                    (*graph)
-                        .clone()
-                        .step_breadth_first_search(&src_in_value, &dest_in_value),
-                ));
+        let ids = graph
+            .id(&src_in_value)
+            .and_then(|src| Ok((src, graph.id(&dest_in_value)?)));
+        let search = ids.and_then(|(src, dest)| match search_type_value.as_ref() {
+            "bfs" => box_search(graph.step_breadth_first_search(src, dest)),
+            "iddfs" => box_search(graph.step_iterative_deepening_search(src, dest)),
+            "bibfs" => box_search(graph.step_bidirectional_breadth_first_search(src, dest)),
+            "dijk" => box_search(graph.step_shortest_path(src, dest)),
+            "bidijk" => box_search(graph.step_bidirectional_shortest_path(src, dest)),
+            "astar" => box_search(graph.step_astar(src, dest)),
+            "greedy" => box_search(graph.step_greedy_best_first_search(src, dest)),
+            "beam" => box_search(graph.step_beam_search(src, dest, BEAM_WIDTH)),
+            "bellman" => box_search(graph.step_bellman_ford(src, dest)),
+            _ => box_search(graph.step_depth_first_search(src, dest)),
+        });
+
+        let search = match search {
+            Ok(search) => {
+                error_text.style().set_property("display", "none").unwrap();
+                show_progress(&progress_text, &*search.borrow());
+                search
             }
-            "dijk" => {
-                search = Box::new(RefCell::new(
-                    (*graph)
-                        .clone()
-                        .step_shortest_path(&src_in_value, &dest_in_value),
-                ));
-            }
-            // all cases must be matched
-            _ => {
-                // search has to be initialized
-                // TODO: come up with a better sulution for this
-                search = Box::new(RefCell::new(
-                    (*graph)
-                        .clone()
-                        .step_depth_first_search(&src_in_value, &dest_in_value),
-                ));
+            Err(_) => {
+                error_text
+                    .style()
+                    .set_property("display", "inline")
+                    .unwrap();
+                return;
             }
-        }
+        };
 
         // Stop current searches
         if *is_searching.borrow() {
@@ -645,7 +689,7 @@ fn make_search_button_listener(
                         .result()
                         .unwrap()
                         .into_iter()
-                        .map(|(s, _)| s)
+                        .map(|(id, _)| graph.name(id).unwrap().to_owned())
                         .collect(),
                 );
 
@@ -657,6 +701,7 @@ fn make_search_button_listener(
                 request_animation_frame(Rc::clone(&search_window), f.borrow().as_ref().unwrap());
             } else if delta > interval {
                 let status: Status = search.borrow_mut().next();
+                show_progress(&progress_text, &*search.borrow());
 
                 // TODO: DELETE ME
                 console::log_2(&"Status: ".into(), &status.to_string().into());
@@ -664,16 +709,19 @@ fn make_search_button_listener(
                 match status {
                     Status::Searching => {
                         // TODO: DELETE ME
-                        console::log_2(&"Current: ".into(), &(*search.borrow().current()).into());
+                        console::log_2(
+                            &"Current: ".into(),
+                            &current_name(graph, &*search.borrow()).into(),
+                        );
                         canvas_state
                             .borrow_mut()
-                            .set_active(Some(search.borrow().current().to_owned()));
+                            .set_active(current_name(graph, &*search.borrow()));
                         canvas_state.borrow_mut().set_queued(
                             search
                                 .borrow()
                                 .visible()
                                 .iter()
-                                .map(|(id, _)| id.to_string())
+                                .map(|(id, _)| graph.name(*id).unwrap().to_owned())
                                 .collect(),
                         );
                         canvas_state.borrow_mut().set_searched(
@@ -681,7 +729,7 @@ fn make_search_button_listener(
                                 .borrow()
                                 .visited()
                                 .iter()
-                                .map(|(id, _)| id.to_string())
+                                .map(|(id, _)| graph.name(*id).unwrap().to_owned())
                                 .collect(),
                         );
                         canvas_state.borrow().draw(&canvas, &context);
@@ -695,13 +743,13 @@ fn make_search_button_listener(
                     Status::Found => {
                         canvas_state
                             .borrow_mut()
-                            .set_active(Some(search.borrow().current().to_owned()));
+                            .set_active(current_name(graph, &*search.borrow()));
                         canvas_state.borrow_mut().set_queued(
                             search
                                 .borrow()
                                 .visible()
                                 .iter()
-                                .map(|(id, _)| id.to_string())
+                                .map(|(id, _)| graph.name(*id).unwrap().to_owned())
                                 .collect(),
                         );
                         canvas_state.borrow_mut().set_searched(
@@ -709,7 +757,7 @@ fn make_search_button_listener(
                                 .borrow()
                                 .visited()
                                 .iter()
-                                .map(|(id, _)| id.to_string())
+                                .map(|(id, _)| graph.name(*id).unwrap().to_owned())
                                 .collect(),
                         );
                         canvas_state.borrow().draw(&canvas, &context);
@@ -743,6 +791,126 @@ fn make_search_button_listener(
     }) as Box<dyn FnMut(_)>)
 }
 
+// The canvas an animation draws on, along with what's drawn there.
+struct Board {
+    canvas: Rc<web_sys::HtmlCanvasElement>,
+    context: Rc<web_sys::CanvasRenderingContext2d>,
+    state: Rc<RefCell<CanvasState>>,
+}
+
+// Steps through building a spanning tree, drawing the edges still in the
+// running and the ones accepted so far.
+fn animate_spanning_tree(
+    window: Rc<web_sys::Window>,
+    performance: Rc<web_sys::Performance>,
+    board: Board,
+    graph: &'static Graph,
+    is_searching: Rc<RefCell<bool>>,
+    search_stop: Rc<RefCell<bool>>,
+    tree: Box<RefCell<dyn SpanningTree>>,
+) {
+    let mut then = performance.now();
+    let interval = consts::DEFAULT_SPEED;
+    let mut end = false;
+
+    let tree_window = Rc::clone(&window);
+    let f = Rc::new(RefCell::new(None));
+    let g = f.clone();
+
+    *g.borrow_mut() = Some(Closure::wrap(Box::new(move || {
+        let now = performance.now();
+        let delta = now - then;
+
+        if end || *search_stop.borrow() {
+            is_searching.replace(false);
+            search_stop.replace(false);
+            let _ = f.borrow_mut().take();
+            return;
+        }
+
+        if delta > interval {
+            let status = tree.borrow_mut().next();
+            let tree = tree.borrow();
+            let name = |id| graph.name(id).unwrap().to_owned();
+            let conns = |edges: Vec<TreeEdge>| -> HashSet<Conn> {
+                edges
+                    .into_iter()
+                    .map(|(from, to, _)| Conn(name(from), name(to)))
+                    .collect()
+            };
+
+            let accepted = tree.accepted();
+            let in_tree: HashSet<String> = accepted
+                .iter()
+                .flat_map(|(from, to, _)| vec![name(*from), name(*to)])
+                .collect();
+
+            let mut canvas_state = board.state.borrow_mut();
+            match status {
+                Status::Searching => {
+                    let frontier = tree.frontier();
+                    let queued = frontier
+                        .iter()
+                        .flat_map(|(from, to, _)| vec![name(*from), name(*to)])
+                        .filter(|name| !in_tree.contains(name))
+                        .collect();
+
+                    canvas_state.set_active(tree.current().map(|(_, to, _)| name(to)));
+                    canvas_state.set_queued(queued);
+                    canvas_state.set_frontier(conns(frontier));
+                }
+                _ => {
+                    canvas_state.set_active(None);
+                    canvas_state.set_queued(HashSet::new());
+                    canvas_state.set_frontier(HashSet::new());
+                    end = true;
+                }
+            }
+            canvas_state.set_searched(in_tree);
+            canvas_state.set_tree(conns(accepted));
+            canvas_state.draw(&board.canvas, &board.context);
+
+            then = now - (delta % interval);
+        }
+
+        request_animation_frame(Rc::clone(&tree_window), f.borrow().as_ref().unwrap());
+    }) as Box<dyn FnMut()>));
+
+    request_animation_frame(Rc::clone(&window), g.borrow().as_ref().unwrap());
+}
+
+fn box_spanning_tree<T: SpanningTree + 'static>(
+    tree: Result<T, GraphError>,
+) -> Result<Box<RefCell<dyn SpanningTree>>, GraphError> {
+    tree.map(|tree| Box::new(RefCell::new(tree)) as Box<RefCell<dyn SpanningTree>>)
+}
+
+fn box_search<S: Search + 'static>(
+    search: Result<S, GraphError>,
+) -> Result<Box<RefCell<dyn Search>>, GraphError> {
+    search.map(|search| Box::new(RefCell::new(search)) as Box<RefCell<dyn Search>>)
+}
+
+// The depth limit and re-expansions, for the searches that have them, so
+// iterative deepening's trade of time for memory can be seen as it runs.
+fn show_progress(progress_text: &web_sys::HtmlElement, search: &dyn Search) {
+    let mut progress = Vec::new();
+    if let Some(limit) = search.depth_limit() {
+        progress.push(format!("Depth limit: {}", limit));
+    }
+    if let Some(re_expansions) = search.re_expansions() {
+        progress.push(format!("Re-expansions: {}", re_expansions));
+    }
+
+    progress_text.set_inner_html(&progress.join(", "));
+}
+
+fn current_name(graph: &Graph, search: &dyn Search) -> Option<String> {
+    search
+        .current()
+        .map(|id| graph.name(id).unwrap().to_owned())
+}
+
 fn request_animation_frame(window: Rc<web_sys::Window>, f: &Closure<FnMut()>) {
     window
         .request_animation_frame(f.as_ref().unchecked_ref())